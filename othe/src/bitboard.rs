@@ -0,0 +1,97 @@
+//! Bitboard routines, a bitboard is a `u64` where the bit `row * 8 + col` is
+//! set if there is a disc on the square `(col, row)`.
+
+/// The squares of the `a` column.
+pub const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+/// The squares of the `h` column.
+pub const FILE_H: u64 = 0x8080_8080_8080_8080;
+
+/// The directions as `(shift, mask)`, the bitboard is shifted left by `shift`
+/// if it is positive and right otherwise, then the `mask` removes the discs
+/// that wrapped around the edges of the board.
+const DIRECTIONS: [(i32, u64); 8] = [
+    (-9, !FILE_H), // LEFT-UP
+    (-8, !0),      // UP
+    (-7, !FILE_A), // RIGHT-UP
+    (-1, !FILE_H), // LEFT
+    (1, !FILE_A),  // RIGHT
+    (7, !FILE_H),  // LEFT-DOWN
+    (8, !0),       // DOWN
+    (9, !FILE_A),  // RIGHT-DOWN
+];
+
+/// Shift the `bitboard` by one square in the direction `(shift, mask)`.
+#[inline]
+fn shift(bitboard: u64, (shift, mask): (i32, u64)) -> u64 {
+    if shift > 0 {
+        (bitboard << shift) & mask
+    } else {
+        (bitboard >> -shift) & mask
+    }
+}
+
+/// Compute the discs of the opponent flipped if the player plays at the
+/// square `sq` (index between 0 and 63). `player_bb` and `opp_bb` are the
+/// bitboards of the player and his opponent.
+#[must_use]
+pub fn flips(player_bb: u64, opp_bb: u64, sq: u8) -> u64 {
+    flips_by_direction(player_bb, opp_bb, sq)
+        .into_iter()
+        .fold(0, |flipped, line| flipped | line)
+}
+
+/// Like [`flips`] but the flipped discs are separated by direction, in the
+/// order: left-up, up, right-up, left, right, left-down, down and right-down.
+#[must_use]
+pub fn flips_by_direction(player_bb: u64, opp_bb: u64, sq: u8) -> [u64; 8] {
+    debug_assert!(sq < 64);
+    let mut flipped = [0; 8];
+
+    for (dir_flipped, dir) in flipped.iter_mut().zip(DIRECTIONS) {
+        // the opponent's discs on the line, from the square
+        let mut line = 0;
+        let mut cur = shift(1 << sq, dir);
+
+        while cur & opp_bb != 0 {
+            line |= cur;
+            cur = shift(cur, dir);
+        }
+
+        // the line is outflanked if it ends with a disc of the player
+        if cur & player_bb != 0 {
+            *dir_flipped = line;
+        }
+    }
+
+    flipped
+}
+
+/// Compute the legal moves of the player, `player_bb` and `opp_bb` are the
+/// bitboards of the player and his opponent.
+#[must_use]
+pub fn moves(player_bb: u64, opp_bb: u64) -> u64 {
+    let empty = !(player_bb | opp_bb);
+    let mut moves = 0;
+
+    for dir in DIRECTIONS {
+        // the opponent's discs on a line starting from a disc of the player,
+        // there is at most six of them.
+        let mut line = shift(player_bb, dir) & opp_bb;
+        for _ in 0..5 {
+            line |= shift(line, dir) & opp_bb;
+        }
+
+        moves |= shift(line, dir) & empty;
+    }
+
+    moves
+}
+
+/// Return the squares adjacent to at least one square of the `bitboard`.
+#[must_use]
+pub fn neighbours(bitboard: u64) -> u64 {
+    DIRECTIONS
+        .iter()
+        .fold(0, |acc, &dir| acc | shift(bitboard, dir))
+}
@@ -1,26 +1,43 @@
 use std::{
     borrow::Cow,
     cell::RefCell,
+    cmp::Ordering,
+    collections::HashSet,
     env,
     error::Error,
     fmt::{self, Display},
     fs::{self, File},
-    io::{self, Write},
+    io::{self, BufRead, BufWriter, IsTerminal, Read, Write},
     ops::Not,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 use lazy_static::lazy_static;
-use serde::{Deserialize, Serialize};
-use termcolor::{StandardStream, WriteColor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use termcolor::{NoColor, StandardStream, WriteColor};
 
-use player::{Player, PlayerType, ReplayPlayer};
+use messages::Messages;
+use player::{BuiltinBot, HumanPlayer, Player, PlayerType, ReplayPlayer, ThinkOutcome};
+use search::SearchDepth;
+use stats::PlayerStats;
+use style::{BorderStyle, RenderStyle};
 
+pub mod bitboard;
+pub mod messages;
+pub mod opening;
 pub mod player;
+pub mod puzzle;
+pub mod search;
+pub mod stats;
 pub mod style;
+pub mod wthor;
 
 pub const VERSION_AND_GIT_HASH: &str = env!("VERSION_AND_GIT_HASH");
 pub const LICENSE: &str = include_str!("../../LICENSE");
@@ -62,7 +79,23 @@ pub enum OthelloError {
     InvalidLenghtOfNotation,
     InvalidCharInNotation { ch: char },
     InvalidPlayerType,
+    InvalidSaveNumber(String),
+    InvalidWthorRecord(&'static str),
+    InvalidHandicap,
+    InvalidStartKind,
+    InvalidPlayerColor(String),
+    Panicked(String),
+    ForcedPassNotAllowed,
+    EndOfInput,
+    InvalidMarker(char),
+    InvalidPly(usize),
+    OverlappingBitboards(u64),
+    IllegalMoveInSequence { index: usize, mov: Move },
+    IllegalBotMove { mov: Move, legal_moves: Vec<Move> },
+    ReplayMismatch { expected: State, got: State },
+    RepeatedPosition { ply: usize },
     SerdeJsonError(serde_json::Error),
+    SerdeRonError(ron::Error),
 }
 
 impl Error for OthelloError {}
@@ -77,7 +110,26 @@ impl Display for OthelloError {
             OthelloError::InvalidLenghtOfNotation => write!(f, "the Othello Notation must be 64 characters long"),
             OthelloError::InvalidCharInNotation { ch } => write!(f, "invalid character {ch:?} in Othello Notation"),
             OthelloError::InvalidPlayerType => write!(f, "Invalid player type."),
+            OthelloError::InvalidSaveNumber(num) => write!(f, "there is no save with the number {num:?}"),
+            OthelloError::InvalidWthorRecord(reason) => write!(f, "invalid WTHOR game record: {reason}"),
+            OthelloError::InvalidHandicap => write!(f, "invalid handicap, it must be between 0 and 4 corners given to Black or White"),
+            OthelloError::InvalidStartKind => write!(f, "Invalid starting layout."),
+            OthelloError::Panicked(msg) => write!(f, "INTERNAL ERROR: the command panicked: {msg}"),
+            OthelloError::InvalidPlayerColor(color) => write!(f, "invalid player color {color:?}, it must be `black` or `white`"),
+            OthelloError::ForcedPassNotAllowed => write!(f, "forced passes are not allowed by the game settings"),
+            OthelloError::EndOfInput => write!(f, "reached the end of the input"),
+            OthelloError::InvalidMarker(ch) => write!(f, "{ch:?} can't be the legal moves marker, it must be a visible character one column wide"),
+            OthelloError::IllegalMoveInSequence { index, mov } => write!(f, "the move {} ({}) is illegal", index + 1, mov.to_algebric()),
+            OthelloError::IllegalBotMove { mov, legal_moves } => {
+                let legal_moves = legal_moves.iter().map(|mov| mov.to_algebric()).collect::<Vec<_>>();
+                write!(f, "the bot played the illegal move {}, the legal moves were: {}", mov.to_algebric(), legal_moves.join(", "))
+            }
+            OthelloError::OverlappingBitboards(overlap) => write!(f, "the squares {overlap:#018x} are set in both bitboards"),
+            OthelloError::ReplayMismatch { expected, got } => write!(f, "the replayed game ended with {got:?} instead of the recorded {expected:?}"),
+            OthelloError::RepeatedPosition { ply } => write!(f, "INTERNAL ERROR: the position after the move {ply} was already reached, a move was applied wrongly"),
+            OthelloError::InvalidPly(ply) => write!(f, "can't go back to move {ply}, it wasn't played yet"),
             OthelloError::SerdeJsonError(e) => write!(f, "SERIALIZATION ERROR: {e}"),
+            OthelloError::SerdeRonError(e) => write!(f, "SERIALIZATION ERROR: {e}"),
         }
     }
 }
@@ -94,7 +146,19 @@ impl From<serde_json::Error> for OthelloError {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl From<ron::Error> for OthelloError {
+    fn from(value: ron::Error) -> Self {
+        OthelloError::SerdeRonError(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for OthelloError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        OthelloError::SerdeRonError(value.code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Disc {
     White,
     Black,
@@ -125,6 +189,35 @@ impl Display for Disc {
     }
 }
 
+/// The disc is serialized as a single character, `B` for black, `W` for white
+/// and `-` for empty.
+impl Serialize for Disc {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Disc::White => "W",
+            Disc::Black => "B",
+            Disc::Empty => "-",
+        })
+    }
+}
+
+/// The disc is deserialized from its single character or from its full name,
+/// as it was serialized before.
+impl<'de> Deserialize<'de> for Disc {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Disc, D::Error> {
+        let disc = Cow::<'de, str>::deserialize(deserializer)?;
+        match disc.as_ref() {
+            "W" | "White" => Ok(Disc::White),
+            "B" | "Black" => Ok(Disc::Black),
+            "-" | "Empty" => Ok(Disc::Empty),
+            _ => Err(serde::de::Error::unknown_variant(
+                &disc,
+                &["W", "B", "-", "White", "Black", "Empty"],
+            )),
+        }
+    }
+}
+
 static DIRECTIONS: [(i32, i32); 8] = [
     (-1, -1), // RIGHT UP
     (0, -1),  // UP
@@ -136,12 +229,62 @@ static DIRECTIONS: [(i32, i32); 8] = [
     (1, 1),   // RIGHT-DOWN
 ];
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// The result of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// The player with this color has more discs.
+    Win(Disc),
+    /// Both players have the same number of discs.
+    Draw,
+}
+
+/// What happens on the turn of a player, see [`Board::terminal_or_pass`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnOutcome {
+    /// The player can move.
+    Continue {
+        /// The legal moves of the player.
+        legal_moves: u64,
+    },
+    /// The player can't move but his opponent can, his turn is forfeited.
+    Pass {
+        /// The legal moves of the opponent, on his next turn.
+        opponent_moves: u64,
+    },
+    /// No player can move, the game is over.
+    GameOver(Outcome),
+}
+
+/// The layout of the four center discs at the start of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StartKind {
+    /// The standard layout, the discs of the same color are on a diagonal.
+    #[default]
+    Diagonal,
+    /// The discs of the same color are side by side on a column.
+    Parallel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Board {
     squares: [Disc; 64],
 }
 
 impl Board {
+    /// The four corners, `a1`, `h1`, `a8` and `h8`.
+    pub const CORNERS: u64 = 0x8100_0000_0000_0081;
+
+    /// The squares diagonally adjacent to the corners, `b2`, `g2`, `b7` and
+    /// `g7`.
+    pub const X_SQUARES: u64 = 0x0042_0000_0000_4200;
+
+    /// The edge squares adjacent to the corners, `b1`, `g1`, `a2`, `h2`, `a7`,
+    /// `h7`, `b8` and `g8`.
+    pub const C_SQUARES: u64 = 0x4281_0000_0000_8142;
+
+    /// The squares on the edges of the board, corners included.
+    pub const EDGES: u64 = 0xFF81_8181_8181_81FF;
+
     /// Create a new board with the starting layout
     pub const fn new() -> Board {
         use Disc::Black as B;
@@ -161,6 +304,93 @@ impl Board {
         }
     }
 
+    /// Create a board with only the listed discs, starting from an empty
+    /// board.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the coordinates of a move are out of the board.
+    #[track_caller]
+    pub fn from_discs(placements: &[(Move, Disc)]) -> Board {
+        let mut board = Board {
+            squares: [Disc::Empty; 64],
+        };
+        for &(mov, disc) in placements {
+            board.change_disc(mov, disc);
+        }
+        board
+    }
+
+    /// Create a board from its rows, from the top to the bottom, the columns
+    /// are from the left to the right like in the notation. Any arrangement
+    /// of the discs is allowed, e.g: to write positions by hand.
+    pub const fn from_rows(rows: [[Disc; 8]; 8]) -> Board {
+        let mut squares = [Disc::Empty; 64];
+        let mut idx = 0;
+        while idx < 64 {
+            squares[idx] = rows[idx / 8][idx % 8];
+            idx += 1;
+        }
+        Board { squares }
+    }
+
+    /// Create a board from the bitboards of the black and white discs, bit
+    /// `row * 8 + col` is the square at `(col, row)`. This is the
+    /// representation used by most Othello engines.
+    pub fn from_bitboards(black: u64, white: u64) -> Result<Board> {
+        if black & white != 0 {
+            return Err(OthelloError::OverlappingBitboards(black & white));
+        }
+
+        let mut board = Board {
+            squares: [Disc::Empty; 64],
+        };
+        for idx in bits(black) {
+            board.squares[idx as usize] = Disc::Black;
+        }
+        for idx in bits(white) {
+            board.squares[idx as usize] = Disc::White;
+        }
+        Ok(board)
+    }
+
+    /// Create a new board with the `start` layout of the center discs.
+    pub fn with_start(start: StartKind) -> Board {
+        let mut board = Board::new();
+        if start == StartKind::Parallel {
+            // White on d4 and d5, Black on e4 and e5
+            board.squares[35] = Disc::White;
+            board.squares[36] = Disc::Black;
+        }
+        board
+    }
+
+    /// Create a new board with the `start` layout and `corners` corners given
+    /// to `player` as a handicap, they are given in the order `a1`, `h8`, `h1`
+    /// and `a8`. The board must leave a legal move to the `first_player`.
+    pub fn with_handicap(
+        start: StartKind,
+        player: Disc,
+        corners: u8,
+        first_player: Disc,
+    ) -> Result<Board> {
+        if player == Disc::Empty || first_player == Disc::Empty || corners > 4 {
+            return Err(OthelloError::InvalidHandicap);
+        }
+
+        let mut board = Board::with_start(start);
+        for idx in [0, 63, 7, 56].into_iter().take(corners as usize) {
+            board.squares[idx] = player;
+        }
+
+        // the first player must be able to play
+        if board.legal_moves(first_player) == 0 {
+            return Err(OthelloError::InvalidHandicap);
+        }
+
+        Ok(board)
+    }
+
     /// Get the disc located at those X and Y coordinates, check if coordinates
     /// are in bounds
     #[inline]
@@ -212,6 +442,34 @@ impl Board {
         (white, black, empty)
     }
 
+    /// Return the number of empty squares on the board, see [`Board::empties`]
+    /// for their bitfield.
+    #[must_use]
+    pub fn empty_count(&self) -> u8 {
+        let (_, _, empty) = self.scores();
+        empty
+    }
+
+    /// Return the number of discs of `player` minus the number of discs of
+    /// his opponent.
+    #[must_use]
+    pub fn disc_diff(&self, player: Disc) -> i32 {
+        let (white, black, _) = self.scores();
+        let diff = black as i32 - white as i32;
+        match player {
+            Disc::Black => diff,
+            Disc::White => -diff,
+            Disc::Empty => panic!("The player should not be an empty disc."),
+        }
+    }
+
+    /// Return the number of discs on the board, the four discs of the start
+    /// included.
+    #[must_use]
+    pub fn discs_placed(&self) -> u8 {
+        64 - self.empty_count()
+    }
+
     /// Return the current legal moves for the `player` into a bitfield format.
     ///
     /// The first bit of the bitfield is the first disc at index 0 and the last
@@ -281,13 +539,26 @@ impl Board {
     /// [`legal_moves`] method.
     ///
     /// [`legal_moves`]: Board::legal_moves
-    pub fn move_outflanks(&self, player: Disc, Move { col: x, row: y }: Move) -> u64 {
-        let mut bitfield = 0;
-
+    pub fn move_outflanks(&self, player: Disc, mov: Move) -> u64 {
         if player == Disc::Empty {
             panic!("The player should not be an empty disc.")
         }
 
+        bitboard::flips(
+            self.bitboard(player),
+            self.bitboard(!player),
+            mov.into_idx() as u8,
+        )
+    }
+
+    /// The scan-based implementation of [`move_outflanks`] before the
+    /// bitboards, the bitboard flips are tested against it.
+    ///
+    /// [`move_outflanks`]: Board::move_outflanks
+    #[cfg(test)]
+    fn move_outflanks_scan(&self, player: Disc, Move { col: x, row: y }: Move) -> u64 {
+        let mut bitfield = 0;
+
         for (dx, dy) in DIRECTIONS {
             let mut nx = x as i32 + dx;
             let mut ny = y as i32 + dy;
@@ -304,8 +575,9 @@ impl Board {
                     break;
                 }
 
-                if self.squares[n_idx] == player && may_outflank != 0 {
-                    // We are able to outflank at least one opponent's disc
+                if self.squares[n_idx] == player {
+                    // We are able to outflank the opponent's discs if there is
+                    // at least one between the move and this disc
                     bitfield |= may_outflank;
                     break;
                 }
@@ -318,676 +590,5182 @@ impl Board {
         bitfield
     }
 
-    /// Put the discs (`player` arg) according to the provided bitfield.
+    /// Compute the discs that will be outflanked from a move, separated by
+    /// direction, e.g: to draw the capturing rays of the move. The directions
+    /// are in the order: left-up, up, right-up, left, right, left-down, down
+    /// and right-down; the directions without capture are `0`.
     ///
-    /// The first bit of the bitfield is the first disc at index 0 and the last
-    /// bit is index 63. (just like legal_moves)
-    pub fn put_discs(&mut self, bitfield: u64, player: Disc) {
-        for i in 0..self.squares.len() {
-            if (1_u64 << i & bitfield) != 0 {
-                self.squares[i] = player;
-            }
+    /// The union of the bitfields is [`move_outflanks`].
+    ///
+    /// [`move_outflanks`]: Board::move_outflanks
+    #[must_use]
+    pub fn outflanks_by_direction(&self, player: Disc, mov: Move) -> [u64; 8] {
+        if player == Disc::Empty {
+            panic!("The player should not be an empty disc.")
         }
+
+        bitboard::flips_by_direction(
+            self.bitboard(player),
+            self.bitboard(!player),
+            mov.into_idx() as u8,
+        )
     }
-}
 
-impl Default for Board {
-    #[inline]
-    fn default() -> Self {
-        Board::new()
+    /// Return the bitfield of the empty squares, in the same order as
+    /// [`Board::legal_moves`]. See [`Board::empty_count`] for their number.
+    #[must_use]
+    pub fn empties(&self) -> u64 {
+        self.bitboard(Disc::Empty)
     }
-}
 
-impl FromStr for Board {
-    type Err = OthelloError;
+    /// Return the bitfield of the squares with a disc, the complement of
+    /// [`Board::empties`].
+    #[must_use]
+    pub fn occupied(&self) -> u64 {
+        !self.empties()
+    }
 
-    /// The board can be constructed from a string, [the format is common to
-    /// othello programs.][this-articles]
-    ///
-    /// `XO---XXX-OOO-OOO-OOOOOO---OOXO---OOXOOO-OOXOOOOOXXXXX---XXXXXX--`
-    /// In this notation, dashes (`-`) represent empty squares, `X` represent
-    /// black discs and `O` represent White's discs. The string is 64 character
-    /// long.
-    ///
-    /// [this-article]: https://mirror.math.princeton.edu/pub/CTAN/macros/latex/contrib/othelloboard/othelloboard.pdf#page=8
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // TODO: write tests for this function
-        if s.len() != 64 {
-            return Err(OthelloError::InvalidLenghtOfNotation);
-        }
-        let mut board = [Disc::Empty; 64];
-        for (i, c) in s.char_indices() {
-            match c {
-                '-' =>
-                    /* we do nothing because it is already an empty square*/
-                    {}
-                'O' => board[i] = Disc::White,
-                'X' => board[i] = Disc::Black,
-                ch => {
-                    return Err(OthelloError::InvalidCharInNotation { ch });
+    /// Return the bitfield of the black discs.
+    #[must_use]
+    pub fn black_bb(&self) -> u64 {
+        self.bitboard(Disc::Black)
+    }
+
+    /// Return the bitfield of the white discs.
+    #[must_use]
+    pub fn white_bb(&self) -> u64 {
+        self.bitboard(Disc::White)
+    }
+
+    /// Return the bitboards of the black and white discs, see
+    /// [`Board::from_bitboards`].
+    #[must_use]
+    pub fn to_bitboards(&self) -> (u64, u64) {
+        (self.bitboard(Disc::Black), self.bitboard(Disc::White))
+    }
+
+    /// Write the board in 16 bytes, the bitboards of the black and then the
+    /// white discs in little-endian, see [`Board::from_bytes`].
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let (black, white) = self.to_bitboards();
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&black.to_le_bytes());
+        bytes[8..].copy_from_slice(&white.to_le_bytes());
+        bytes
+    }
+
+    /// Read a board written by [`Board::to_bytes`], it fails if a square is
+    /// set in both bitboards.
+    pub fn from_bytes(bytes: &[u8; 16]) -> Result<Board> {
+        let (black, white) = bytes.split_at(8);
+        Board::from_bitboards(
+            u64::from_le_bytes(black.try_into().unwrap()),
+            u64::from_le_bytes(white.try_into().unwrap()),
+        )
+    }
+
+    /// Return the bitboard of the discs of the color `disc`.
+    pub(crate) fn bitboard(&self, disc: Disc) -> u64 {
+        self.squares
+            .iter()
+            .enumerate()
+            .filter(|(_, &d)| d == disc)
+            .fold(0, |bb, (idx, _)| bb | 1 << idx)
+    }
+
+    /// Return the number of frontier discs of `player`, the discs that are
+    /// adjacent to at least one empty square.
+    #[must_use]
+    pub fn frontier_discs(&self, player: Disc) -> u32 {
+        let mut count = 0;
+
+        for y in 0..8 {
+            for x in 0..8 {
+                if self.squares[y * 8 + x] != player {
+                    continue;
+                }
+
+                let is_frontier = DIRECTIONS.iter().any(|(dx, dy)| {
+                    let nx = x as i32 + dx;
+                    let ny = y as i32 + dy;
+                    (0..8).contains(&nx)
+                        && (0..8).contains(&ny)
+                        && self.squares[(ny * 8 + nx) as usize] == Disc::Empty
+                });
+
+                if is_frontier {
+                    count += 1;
                 }
             }
         }
-        Ok(Board { squares: board })
+
+        count
     }
-}
 
-/// A position on the Othello Board
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Move {
-    pub col: u8,
-    pub row: u8,
-}
+    /// Return the influence of `player` on each square, indexed by
+    /// `row * 8 + col`. The influence on an empty square is the number of
+    /// discs of `player` in the lines of discs that start next to it, in the
+    /// eight directions until an empty square or the edge of the board. Those
+    /// discs may outflank a line toward the square later. The influence on
+    /// an occupied square is zero.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `player` is [`Disc::Empty`].
+    #[must_use]
+    pub fn influence(&self, player: Disc) -> [u8; 64] {
+        assert_ne!(
+            player,
+            Disc::Empty,
+            "The player should not be an empty disc."
+        );
 
-impl Move {
-    pub fn from_algebric(pos: &str) -> Result<Move> {
-        let (col, row) = algebric2xy(pos)?;
-        Ok(Move { col, row })
+        let mut influence = [0; 64];
+        for y in 0..8 {
+            for x in 0..8 {
+                let idx = y * 8 + x;
+                if self.squares[idx] != Disc::Empty {
+                    continue;
+                }
+
+                for (dx, dy) in DIRECTIONS {
+                    let mut nx = x as i32 + dx;
+                    let mut ny = y as i32 + dy;
+                    while (0..8).contains(&nx) && (0..8).contains(&ny) {
+                        match self.squares[(ny * 8 + nx) as usize] {
+                            Disc::Empty => break,
+                            disc if disc == player => influence[idx] += 1,
+                            _ => {}
+                        }
+                        nx += dx;
+                        ny += dy;
+                    }
+                }
+            }
+        }
+
+        influence
     }
 
-    pub fn into_idx(self) -> usize {
-        self.row as usize * 8 + self.col as usize
+    /// Return the number of corners owned by `player`.
+    #[must_use]
+    pub fn count_corners(&self, player: Disc) -> u32 {
+        (self.bitboard(player) & Board::CORNERS).count_ones()
     }
 
-    pub fn from_idx(idx: u8) -> Move {
-        Move {
-            row: idx / 8,
-            col: idx % 8,
-        }
+    /// Return the number of corners owned by `player`.
+    #[deprecated = "use `Board::count_corners` instead"]
+    #[must_use]
+    pub fn corner_count(&self, player: Disc) -> u32 {
+        self.count_corners(player)
     }
-}
 
-/// Converts an algebric notation like `a1`, `g8`, `b7` etc to `(0, 0)`,
-/// `(6, 7)`, `(1, 6)`.
-fn algebric2xy(pos: &str) -> Result<(u8, u8)> {
-    if pos.len() != 2 {
-        return Err(OthelloError::InvalidAlgebric(pos.to_string()));
+    /// Is the game over on this board? It is when no player can move.
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        self.legal_moves(Disc::Black) == 0 && self.legal_moves(Disc::White) == 0
     }
 
-    let mut it = pos.chars();
-    let col = it.next().unwrap() as u8;
-    let row = it.next().unwrap() as u8;
+    /// Return the outcome of the game on this board, or `None` if the game
+    /// isn't over.
+    #[must_use]
+    pub fn outcome(&self) -> Option<Outcome> {
+        if !self.is_terminal() {
+            return None;
+        }
 
-    if !(b'a'..=b'h').contains(&col) || !(b'1'..=b'8').contains(&row) {
-        return Err(OthelloError::InvalidAlgebric(pos.to_string()));
+        Some(self.disc_count_outcome())
     }
 
-    Ok((col - b'a', row - b'1'))
-}
-
-pub fn bitfield_to_indexes(bitfield: u64) -> Vec<u8> {
-    let mut positions = Vec::new();
-    for i in 0..64 {
-        if (bitfield & (1 << i)) != 0 {
-            positions.push(i);
+    /// Return the outcome of the discs on the board, whether the game is
+    /// over or not.
+    fn disc_count_outcome(&self) -> Outcome {
+        let (white, black, _) = self.scores();
+        match white.cmp(&black) {
+            Ordering::Greater => Outcome::Win(Disc::White),
+            Ordering::Less => Outcome::Win(Disc::Black),
+            Ordering::Equal => Outcome::Draw,
         }
     }
-    positions
-}
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GameSave {
-    /// Title of the save, will be showed in the replay command when selecting
-    pub title: String,
-    /// Black player's type
-    pub black_type: PlayerType,
-    /// White player's type
-    pub white_type: PlayerType,
-    /// Black player's name
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub black_name: Option<Cow<'static, str>>,
-    /// White player's name
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub white_name: Option<Cow<'static, str>>,
-    /// Moves during the game
-    pub moves: Vec<Move>,
-    /// The state of the Game at the end, should not be [`State::Playing`]
-    pub end_state: State,
-}
+    /// Decide what happens on the turn of `side`: he moves, he passes
+    /// because only his opponent can move, or the game is over because no
+    /// one can. The legal moves of each player are computed at most once.
+    #[must_use]
+    pub fn terminal_or_pass(&self, side: Disc) -> TurnOutcome {
+        let legal_moves = self.legal_moves(side);
+        if legal_moves != 0 {
+            return TurnOutcome::Continue { legal_moves };
+        }
 
-impl GameSave {
-    pub fn new(title: String, black: &dyn Player, white: &dyn Player) -> GameSave {
-        GameSave {
-            title,
-            black_type: black.player_type(),
-            white_type: white.player_type(),
-            black_name: black.name(),
-            white_name: white.name(),
-            moves: Vec::new(),
-            end_state: State::Playing,
+        match self.legal_moves(!side) {
+            0 => TurnOutcome::GameOver(self.disc_count_outcome()),
+            opponent_moves => TurnOutcome::Pass { opponent_moves },
         }
     }
 
-    pub fn push_move(&mut self, movemnt: Move) {
-        self.moves.push(movemnt);
+    /// Is `mov` a legal move for `player`? Unlike [`Game::is_legal_move`] it
+    /// doesn't need the legal moves to be computed before.
+    #[must_use]
+    pub fn is_legal(&self, player: Disc, mov: Move) -> bool {
+        self.squares[mov.into_idx()] == Disc::Empty && self.move_outflanks(player, mov) != 0
     }
 
-    pub fn set_end_state(&mut self, state: State) {
-        assert_ne!(state, State::Playing);
-        self.end_state = state;
+    /// Put the disc of `player` at `mov` and flip the discs it outflanks,
+    /// don't check if this move is legal.
+    pub(crate) fn apply_move(&mut self, player: Disc, mov: Move) {
+        self.change_disc(mov, player);
+        let outflanks = self.move_outflanks(player, mov);
+        self.put_discs(outflanks, player);
     }
 
-    /// Serializes the struct into a json string.
+    /// Play the `moves` one after the other, starting with the `first` player,
+    /// a player who can't move passes automatically. Return the player to move
+    /// after the last move.
     ///
-    /// If run in debug, the JSON will be pretty with spaces and newlines but
-    /// if it has been built in release mode it will be compact
-    #[inline]
-    #[track_caller]
-    pub fn to_json(&self) -> String {
-        if cfg!(debug_assertions) {
-            serde_json::to_string_pretty(self)
-        } else {
-            serde_json::to_string(self)
+    /// It fails with the index of the first illegal move, the moves before it
+    /// stay on the board.
+    pub fn play_sequence(&mut self, first: Disc, moves: &[Move]) -> Result<Disc> {
+        let mut turn = first;
+        for (index, &mov) in moves.iter().enumerate() {
+            if self.legal_moves(turn) == 0 {
+                // the player can't play, his turn is forfeited
+                turn = !turn;
+            }
+            if !self.is_legal(turn, mov) {
+                return Err(OthelloError::IllegalMoveInSequence { index, mov });
+            }
+            self.apply_move(turn, mov);
+            turn = !turn;
         }
-        .unwrap()
-    }
 
-    #[inline]
-    pub fn from_json(data: &str) -> Result<GameSave, serde_json::Error> {
-        serde_json::from_str(data)
+        Ok(turn)
     }
 
-    /// Interactively replay a game.
-    pub fn replay(&mut self, stream: StandardStream) -> Result<()> {
-        let moves = Arc::new(Mutex::new(self.moves.clone()));
-        let move_idx = Arc::new(Mutex::new(0_usize));
+    /// Play the moves and the passes of a game from this board, `None` is a
+    /// pass, like [`Board::play_sequence`]. The forfeited turns missing from
+    /// the `entries` are inferred. Return the entries with all the passes of
+    /// the game and the player to move.
+    ///
+    /// It fails with the index of the first illegal move among the moves.
+    pub fn play_with_passes(
+        &mut self,
+        first: Disc,
+        entries: &[Option<Move>],
+    ) -> Result<(Vec<Option<Move>>, Disc)> {
+        let mut turn = first;
+        let mut played = Vec::with_capacity(entries.len());
+        // the index of the move among the moves of the game
+        let mut index = 0;
+        for &entry in entries {
+            let Some(mov) = entry else {
+                played.push(None);
+                turn = !turn;
+                continue;
+            };
+            if self.legal_moves(turn) == 0 {
+                // the player can't play, his turn is forfeited
+                played.push(None);
+                turn = !turn;
+            }
+            if !self.is_legal(turn, mov) {
+                return Err(OthelloError::IllegalMoveInSequence { index, mov });
+            }
+            self.apply_move(turn, mov);
+            played.push(Some(mov));
+            turn = !turn;
+            index += 1;
+        }
 
-        let black_player = ReplayPlayer {
-            moves: moves.clone(),
-            move_idx: move_idx.clone(),
-            color: Disc::Black,
-            player_type: self.black_type,
-            name: self.black_name.clone(),
+        Ok((played, turn))
+    }
+
+    /// Play the `moves` from this board, like [`Board::play_sequence`], and
+    /// return the board after each move.
+    ///
+    /// It fails with the index of the first illegal move.
+    pub fn apply_moves(&self, first: Disc, moves: &[Move]) -> Result<Vec<Board>> {
+        let mut board = self.clone();
+        let mut turn = first;
+        let mut boards = Vec::with_capacity(moves.len());
+        for (index, &mov) in moves.iter().enumerate() {
+            turn = board
+                .play_sequence(turn, &[mov])
+                .map_err(|_| OthelloError::IllegalMoveInSequence { index, mov })?;
+            boards.push(board.clone());
+        }
+
+        Ok(boards)
+    }
+
+    /// Return the end state of the game on this board, when no one can move,
+    /// with the scores counted according to the `rule`. `winner_name` is
+    /// called with the winner's color to get its name.
+    pub(crate) fn end_state(
+        &self,
+        rule: ScoringRule,
+        winner_name: impl FnOnce(Disc) -> String,
+    ) -> State {
+        let (white, black, empty) = self.scores();
+        if white == black {
+            // this is a draw.
+            return State::Draw;
+        }
+        let empty = match rule {
+            ScoringRule::ChampionshipWinnerTakesEmpties => empty,
+            ScoringRule::DiscCountOnly => 0,
         };
-        let white_player = ReplayPlayer {
-            moves: moves.clone(),
-            move_idx: move_idx.clone(),
-            color: Disc::White,
-            player_type: self.white_type,
-            name: self.white_name.clone(),
+        // TODO: here a simple opti is storing `white > black`
+        let winner_score = white.max(black) + empty;
+        let loser_score = white.min(black);
+
+        let winner_color = if white > black {
+            Disc::White
+        } else {
+            Disc::Black
         };
 
-        let mut game = Game::new(
-            Box::new(white_player),
-            Box::new(black_player),
-            stream,
-            GameSettings {
-                show_legal_moves: true,
-                saves_game_dir: None,
-                game_record: false,
-            },
-        );
+        State::Winned {
+            winner_name: winner_name(winner_color),
+            winner_color,
+            winner_score,
+            loser_score,
+            empty_squares: empty,
+        }
+    }
 
-        game.play()?;
-        let game_state = game.state.clone();
-        game.post_play()?;
-        // assert the replay in fact works and get the same result as recorded
-        assert_eq!(game_state, self.end_state);
+    /// Renders the board into a string without colors, the `legal_moves`
+    /// bitfield is used to draw the dots of the legal moves.
+    pub fn render_to_string(&self, legal_moves: Option<u64>, style: RenderStyle) -> String {
+        let mut s = NoColor::new(Vec::new());
 
-        Ok(())
+        // writing to a vector never fails
+        self.render(&mut s, legal_moves, style).unwrap();
+
+        // we only wrote valid UTF-8
+        String::from_utf8(s.into_inner()).unwrap()
     }
-}
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct GameSettings {
-    /// Whetever we show the dots on the board or not
-    ///
-    /// # Default
-    ///
-    /// `true`
-    pub show_legal_moves: bool,
-    /// Where we save the games, if the directory doesn't exists 0the will
-    /// create it.
-    ///
-    /// # Default
-    ///
-    /// [`DEFAULT_GAME_SAVES_DIR`][struct@crate::DEFAULT_GAME_SAVES_DIR]
-    pub saves_game_dir: Option<PathBuf>,
-    /// Do we save the games?
+    /// Renders the board as an html `<table>` with the coordinates on its
+    /// edges. The squares have the `black`, `white` or `empty` class and the
+    /// squares of the `legal_moves` bitfield also have the `legal` class, they
+    /// are styled by the page embedding the table.
+    pub fn to_html(&self, legal_moves: Option<u64>) -> String {
+        let mut html = String::from("<table class=\"othello-board\">\n");
+
+        html.push_str("  <tr><th></th>");
+        for col in 'a'..='h' {
+            html.push_str(&format!("<th>{col}</th>"));
+        }
+        html.push_str("</tr>\n");
+
+        for row in 0..8 {
+            html.push_str(&format!("  <tr><th>{}</th>", row + 1));
+            for col in 0..8 {
+                let idx = row * 8 + col;
+                let class = match self.squares[idx] {
+                    Disc::Black => "black",
+                    Disc::White => "white",
+                    Disc::Empty if legal_moves.is_some_and(|moves| moves & (1 << idx) != 0) => {
+                        "empty legal"
+                    }
+                    Disc::Empty => "empty",
+                };
+                html.push_str(&format!("<td class=\"{class}\"></td>"));
+            }
+            html.push_str("</tr>\n");
+        }
+
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Renders the board with colors, the `legal_moves` bitfield is used to
+    /// draw the dots of the legal moves.
+    pub fn render(
+        &self,
+        s: &mut impl WriteColor,
+        legal_moves: Option<u64>,
+        style: RenderStyle,
+    ) -> io::Result<()> {
+        for row in 0..8 {
+            Board::render_edge(s)?;
+            writeln!(s)?;
+            self.render_row(s, row, legal_moves, style.legal_move_marker())?;
+            writeln!(s)?;
+        }
+        Board::render_edge(s)?;
+        writeln!(s)?;
+        Board::render_columns(s)
+    }
+
+    /// Draw the board with plain characters, without colors, e.g: to embed it
+    /// in a text file. The squares are drawn like [`Board::render`] with the
+    /// edges of the `border` style.
+    #[must_use]
+    pub fn to_ascii_art(&self, border: BorderStyle) -> String {
+        let [top, middle, bottom] = border.edges();
+        let vertical = border.vertical();
+
+        let mut art = String::new();
+        for row in 0..8 {
+            art += if row == 0 { top } else { middle };
+            art.push('\n');
+            for col in 0..8 {
+                art.push(vertical);
+                art += match self.squares[row * 8 + col] {
+                    Disc::White => " W ",
+                    Disc::Black => " B ",
+                    Disc::Empty => "   ",
+                };
+            }
+            art.push(vertical);
+            art += &format!(" {}\n", row + 1);
+        }
+        art += bottom;
+        art += "\n  a   b   c   d   e   f   g   h\n";
+
+        art
+    }
+
+    /// Renders the horizontal edge between two rows, without the newline.
+    fn render_edge(s: &mut impl WriteColor) -> io::Result<()> {
+        s.set_color(&style::BOARD_EDGES)?;
+        write!(s, "+---+---+---+---+---+---+---+---+")?;
+        s.reset()
+    }
+
+    /// Renders the squares of the `row` followed by its number, without the
+    /// newline. The legal moves are drawn with the `marker`.
+    fn render_row(
+        &self,
+        s: &mut impl WriteColor,
+        row: usize,
+        legal_moves: Option<u64>,
+        marker: char,
+    ) -> io::Result<()> {
+        for col in 0..8 {
+            s.set_color(&style::BOARD_EDGES)?;
+            write!(s, "|")?;
+            s.reset()?;
+
+            self.render_square(s, row * 8 + col, legal_moves, marker)?;
+        }
+
+        s.set_color(&style::BOARD_EDGES)?;
+        write!(s, "|")?;
+        s.reset()?;
+
+        s.set_color(&style::WHITE_BOLD)?;
+        write!(s, " {}", row + 1)?;
+        s.reset()
+    }
+
+    /// Renders the content of the square at `idx`, three characters wide.
+    fn render_square(
+        &self,
+        s: &mut impl WriteColor,
+        idx: usize,
+        legal_moves: Option<u64>,
+        marker: char,
+    ) -> io::Result<()> {
+        let is_legal_move = legal_moves.is_some_and(|moves| (1 << idx) & moves != 0);
+
+        match self.squares[idx] {
+            Disc::White => {
+                s.set_color(&style::WHITE_PLAYER)?;
+                write!(s, " W ")?;
+            }
+            Disc::Black => {
+                s.set_color(&style::BLACK_PLAYER)?;
+                write!(s, " B ")?;
+            }
+            Disc::Empty if is_legal_move => {
+                s.set_color(&style::LEGAL_MOVE)?;
+                write!(s, " {} ", marker)?;
+            }
+            Disc::Empty => write!(s, "   ")?,
+        }
+        s.reset()
+    }
+
+    /// Return the bitfield of the squares drawn differently on this board with
+    /// the `legal_moves` than on the `previous` board with its legal moves:
+    /// the squares whose disc changed or whose legal move marker appeared or
+    /// disappeared.
+    #[must_use]
+    pub fn changed_squares(
+        &self,
+        legal_moves: Option<u64>,
+        (previous, previous_legal_moves): (&Board, Option<u64>),
+    ) -> u64 {
+        let discs = (0..64)
+            .filter(|&idx| self.squares[idx] != previous.squares[idx])
+            .fold(0, |bb, idx| bb | 1 << idx);
+
+        discs | (legal_moves.unwrap_or(0) ^ previous_legal_moves.unwrap_or(0))
+    }
+
+    /// Return the squares whose disc differs between this board and `other`,
+    /// in the order of the squares, with the disc on this board and the disc
+    /// on `other`.
+    #[must_use]
+    pub fn diff(&self, other: &Board) -> Vec<(Move, Disc, Disc)> {
+        self.squares
+            .iter()
+            .zip(&other.squares)
+            .enumerate()
+            .filter(|(_, (disc, other_disc))| disc != other_disc)
+            .map(|(idx, (&disc, &other_disc))| (Move::from_idx(idx as u8), disc, other_disc))
+            .collect()
+    }
+
+    /// Renders the board over the `previous` board and its legal moves, drawn
+    /// by [`Board::render`] just above the cursor. Only the changed squares
+    /// are redrawn, the cursor is moved with ANSI escape codes and put back
+    /// where it was. The whole board is rendered below if there is no
+    /// previous board.
     ///
-    /// # Default
+    /// The terminal must support the ANSI cursor addressing, the caller
+    /// decides, e.g: with [`GameSettings::incremental_render`].
+    pub fn render_incremental(
+        &self,
+        s: &mut impl WriteColor,
+        previous: Option<(&Board, Option<u64>)>,
+        legal_moves: Option<u64>,
+        style: RenderStyle,
+    ) -> io::Result<()> {
+        match previous {
+            Some(previous) => {
+                self.render_changed_squares(s, previous, legal_moves, style.legal_move_marker())
+            }
+            None => self.render(s, legal_moves, style),
+        }
+    }
+
+    /// Redraw the squares changed since the `previous` board, see
+    /// [`Board::render_incremental`].
+    fn render_changed_squares(
+        &self,
+        s: &mut impl WriteColor,
+        previous: (&Board, Option<u64>),
+        legal_moves: Option<u64>,
+        marker: char,
+    ) -> io::Result<()> {
+        for idx in bits(self.changed_squares(legal_moves, previous)) {
+            let (row, col) = (idx as usize / 8, idx as usize % 8);
+            // the cursor is on the line after the columns letters, the lines
+            // of the squares alternate with the edges.
+            let up = 17 - 2 * row;
+            write!(s, "\x1b[s\x1b[{up}A\x1b[{}G", 4 * col + 2)?;
+            self.render_square(s, idx as usize, legal_moves, marker)?;
+            write!(s, "\x1b[u")?;
+        }
+        s.flush()
+    }
+
+    /// Renders the letters of the columns, with the newline.
+    fn render_columns(s: &mut impl WriteColor) -> io::Result<()> {
+        s.set_color(&style::WHITE_BOLD)?;
+        writeln!(s, "  a   b   c   d   e   f   g   h")?;
+        s.reset()
+    }
+
+    /// Put the discs (`player` arg) according to the provided bitfield.
     ///
-    /// `true`
-    pub game_record: bool,
+    /// The first bit of the bitfield is the first disc at index 0 and the last
+    /// bit is index 63. (just like legal_moves)
+    pub fn put_discs(&mut self, bitfield: u64, player: Disc) {
+        for i in 0..self.squares.len() {
+            if (1_u64 << i & bitfield) != 0 {
+                self.squares[i] = player;
+            }
+        }
+    }
+
+    /// Write the board in the notation parsed by [`Board::from_str`].
+    #[must_use]
+    pub fn to_notation(&self) -> String {
+        self.squares
+            .iter()
+            .map(|disc| match disc {
+                Disc::Empty => '-',
+                Disc::White => 'O',
+                Disc::Black => 'X',
+            })
+            .collect()
+    }
 }
 
-impl Default for GameSettings {
+impl Default for Board {
+    #[inline]
     fn default() -> Self {
-        GameSettings {
-            show_legal_moves: true,
-            saves_game_dir: DEFAULT_GAME_SAVES_DIR.clone(),
-            game_record: true,
-        }
+        Board::new()
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum State {
-    /// The game is currently being played.
-    Playing,
-    /// One of the player winned the game.
-    Winned {
-        /// Who won the game?
-        winner_color: Disc,
-        /// What's his name?
-        winner_name: String,
-        /// Championship style score, the winner's score include empty squares
-        winner_score: u8,
-        /// Championship style score, the winner's score include empty squares
-        loser_score: u8,
-    },
-    /// The game ended in an equality of scores.
-    Draw,
-    /// The current player cannot play, his turn is forfeited (Rule no. 2)
-    TurnForfeited,
-}
+impl FromStr for Board {
+    type Err = OthelloError;
 
-// TODO: make an option to disable all writes and replace with events.
-#[derive(Debug)]
-pub struct Game {
-    /// Squares of the game
-    board: Board,
-    /// Black player
-    black_player: Box<dyn Player>,
-    /// White player
-    white_player: Box<dyn Player>,
-    /// Who's next turn?
+    /// The board can be constructed from a string, [the format is common to
+    /// othello programs.][this-articles]
     ///
-    /// Note:
+    /// `XO---XXX-OOO-OOO-OOOOOO---OOXO---OOXOOO-OOXOOOOOXXXXX---XXXXXX--`
+    /// In this notation, dashes (`-`) represent empty squares, `X` represent
+    /// black discs and `O` represent White's discs. The string is 64 character
+    /// long.
     ///
-    /// `turn` cannot be `Disc::Empty`.
-    turn: Disc,
-    /// The legal moves of the current player (`turn` field).
-    current_legal_moves: Option<u64>,
-    /// The stream, usualy stdout where we render the game.
-    stream: RefCell<StandardStream>,
-    /// The state of the game
-    state: State,
-    /// Game settings
-    pub settings: GameSettings,
-    /// Game save should only be some if the settings has been enabled
-    save: Option<GameSave>,
+    /// [this-article]: https://mirror.math.princeton.edu/pub/CTAN/macros/latex/contrib/othelloboard/othelloboard.pdf#page=8
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // TODO: write tests for this function
+        if s.len() != 64 {
+            return Err(OthelloError::InvalidLenghtOfNotation);
+        }
+        let mut board = [Disc::Empty; 64];
+        for (i, c) in s.char_indices() {
+            match c {
+                '-' =>
+                    /* we do nothing because it is already an empty square*/
+                    {}
+                'O' => board[i] = Disc::White,
+                'X' => board[i] = Disc::Black,
+                ch => {
+                    return Err(OthelloError::InvalidCharInNotation { ch });
+                }
+            }
+        }
+        Ok(Board { squares: board })
+    }
 }
 
-impl Game {
-    pub fn new(
-        white_player: Box<dyn Player>,
-        black_player: Box<dyn Player>,
-        stream: StandardStream,
-        settings: GameSettings,
-    ) -> Game {
-        Game::with_board(Board::new(), white_player, black_player, stream, settings)
+/// A position on the Othello Board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Move {
+    pub col: u8,
+    pub row: u8,
+}
+
+/// How the squares are labeled in the algebric notation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// The columns are letters and the rows are numbers, e.g: `(5, 4)` is
+    /// `f5`.
+    #[default]
+    Standard,
+    /// The columns are numbers and the rows are letters, e.g: `(5, 4)` is
+    /// `6e`.
+    Transposed,
+}
+
+impl Move {
+    pub fn from_algebric(pos: &str) -> Result<Move> {
+        Move::from_algebric_with(pos, Orientation::Standard)
     }
 
-    pub fn with_board(
-        board: Board,
-        white_player: Box<dyn Player>,
-        black_player: Box<dyn Player>,
-        stream: StandardStream,
-        settings: GameSettings,
-    ) -> Game {
-        let mut game = Game {
-            board,
-            white_player,
-            black_player,
-            turn: Disc::Black,
-            current_legal_moves: None,
-            stream: RefCell::new(stream),
-            state: State::Playing,
-            settings,
-            save: None,
-        };
+    /// Parse a move written in the algebric notation with the `orientation`.
+    pub fn from_algebric_with(pos: &str, orientation: Orientation) -> Result<Move> {
+        let (col, row) = algebric2xy(pos, orientation)?;
+        Ok(Move { col, row })
+    }
 
-        // player init
-        game.white_player.init_color(Disc::White);
-        game.black_player.init_color(Disc::Black);
+    /// Converts the move to its algebric notation, e.g: `(0, 0)` is `a1`.
+    pub fn to_algebric(self) -> String {
+        self.to_algebric_with(Orientation::Standard)
+    }
 
-        // game save init
-        if game.settings.saves_game_dir.is_some() && game.settings.game_record {
-            let dt = Local::now();
-            game.save = Some(GameSave::new(
-                dt.to_rfc3339(),
-                game.black_player.as_ref(),
-                game.white_player.as_ref(),
+    /// Converts the move to its algebric notation with the `orientation`.
+    pub fn to_algebric_with(self, orientation: Orientation) -> String {
+        match orientation {
+            Orientation::Standard => {
+                let col = (b'a' + self.col) as char;
+                let row = (b'1' + self.row) as char;
+                format!("{col}{row}")
+            }
+            Orientation::Transposed => {
+                let col = (b'1' + self.col) as char;
+                let row = (b'a' + self.row) as char;
+                format!("{col}{row}")
+            }
+        }
+    }
+
+    pub fn into_idx(self) -> usize {
+        self.row as usize * 8 + self.col as usize
+    }
+
+    pub fn from_idx(idx: u8) -> Move {
+        Move {
+            row: idx / 8,
+            col: idx % 8,
+        }
+    }
+}
+
+/// Converts an algebric notation like `a1`, `g8`, `b7` etc to `(0, 0)`,
+/// `(6, 7)`, `(1, 6)`, or `1a`, `7h`, `2g` with the transposed `orientation`.
+fn algebric2xy(pos: &str, orientation: Orientation) -> Result<(u8, u8)> {
+    let &[first, second] = pos.as_bytes() else {
+        return Err(OthelloError::InvalidAlgebric(pos.to_string()));
+    };
+
+    let (col, row) = match orientation {
+        Orientation::Standard => (first.wrapping_sub(b'a'), second.wrapping_sub(b'1')),
+        Orientation::Transposed => (first.wrapping_sub(b'1'), second.wrapping_sub(b'a')),
+    };
+    if col >= 8 || row >= 8 {
+        return Err(OthelloError::InvalidAlgebric(pos.to_string()));
+    }
+
+    Ok((col, row))
+}
+
+/// Write the `msg` of a prompt and flush the stream, so the prompt is always
+/// shown before the input is read.
+pub fn prompt(s: &mut impl Write, msg: impl Display) -> io::Result<()> {
+    write!(s, "{msg}")?;
+    s.flush()
+}
+
+/// Read a line from the `input` without the newline character, return `None`
+/// at the end of the input, e.g: when the user types Ctrl-D.
+pub fn read_input(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    // pop the newline character
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(line))
+}
+
+/// Check that a turn of `player` changed the scores from `before` to `after`
+/// like a legal move should: exactly one disc is placed on an empty square and
+/// the outflanked discs only change their color, they never appear or vanish.
+///
+/// Only run in debug builds, it panics if the invariant is broken.
+#[cfg(debug_assertions)]
+#[track_caller]
+fn check_turn_invariant(before: (u8, u8, u8), after: (u8, u8, u8), player: Disc) {
+    let (white_before, black_before, empty_before) = before;
+    let (white_after, black_after, empty_after) = after;
+    let (player_before, player_after, opponent_before, opponent_after) = match player {
+        Disc::White => (white_before, white_after, black_before, black_after),
+        Disc::Black => (black_before, black_after, white_before, white_after),
+        Disc::Empty => unreachable!(),
+    };
+
+    assert_eq!(
+        empty_before,
+        empty_after + 1,
+        "a turn must place exactly one disc on the board"
+    );
+    assert!(
+        opponent_after <= opponent_before,
+        "the opponent can't gain discs during a turn"
+    );
+    assert_eq!(
+        player_after - player_before,
+        opponent_before - opponent_after + 1,
+        "outflanked discs must only change their color"
+    );
+}
+
+/// Iterator over the indexes of the set bits of a bitfield, from the lowest
+/// to the highest, see [`bits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitIter(u64);
+
+impl Iterator for BitIter {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+        let idx = self.0.trailing_zeros() as u8;
+        // clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(idx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BitIter {}
+
+/// Iterate over the indexes of the set bits of `bitfield` without allocating.
+#[inline]
+pub fn bits(bitfield: u64) -> BitIter {
+    BitIter(bitfield)
+}
+
+pub fn bitfield_to_indexes(bitfield: u64) -> Vec<u8> {
+    bits(bitfield).collect()
+}
+
+/// Return the `moves` in order with the `passes`, `None` is a pass. The
+/// passes are the number of moves played before each of them.
+fn moves_with_passes(moves: &[Move], passes: &[usize]) -> Vec<Option<Move>> {
+    let mut passes = passes.iter().peekable();
+    let mut entries = Vec::with_capacity(moves.len() + passes.len());
+    for ply in 0..=moves.len() {
+        while passes.next_if(|&&pass| pass == ply).is_some() {
+            entries.push(None);
+        }
+        if let Some(&mov) = moves.get(ply) {
+            entries.push(Some(mov));
+        }
+    }
+    entries
+}
+
+/// The first difference between the moves of two games, see [`GameSave::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovesDiff {
+    /// Index of the first move that differs, starting at zero.
+    pub ply: usize,
+    /// The move of the first game at `ply`, `None` if the game was already
+    /// over.
+    pub left: Option<Move>,
+    /// The move of the second game at `ply`, `None` if the game was already
+    /// over.
+    pub right: Option<Move>,
+}
+
+/// A move of a recorded game with the position after it, see
+/// [`GameSave::positions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayedMove {
+    /// The color of the player who played the move.
+    pub player: Disc,
+    /// The move.
+    pub mov: Move,
+    /// Bitfield of the discs flipped by the move.
+    pub flips: u64,
+    /// The board after the move.
+    pub board: Board,
+}
+
+/// Iterator over the moves of a recorded game, see [`GameSave::positions`].
+#[derive(Debug, Clone)]
+pub struct Positions {
+    entries: std::vec::IntoIter<Option<Move>>,
+    board: Board,
+    turn: Disc,
+}
+
+impl Iterator for Positions {
+    type Item = PlayedMove;
+
+    fn next(&mut self) -> Option<PlayedMove> {
+        // the turn of the player is passed
+        let mov = loop {
+            match self.entries.next()? {
+                Some(mov) => break mov,
+                None => self.turn = !self.turn,
+            }
+        };
+
+        // the passes aren't stored in the old saves
+        if self.board.legal_moves(self.turn) == 0 {
+            // the player can't play, his turn is forfeited
+            self.turn = !self.turn;
+        }
+        let flips = self.board.move_outflanks(self.turn, mov);
+        self.board.apply_move(self.turn, mov);
+
+        let played = PlayedMove {
+            player: self.turn,
+            mov,
+            flips,
+            board: self.board.clone(),
+        };
+        self.turn = !self.turn;
+        Some(played)
+    }
+}
+
+/// The evaluation of the position before and after a move of a recorded game,
+/// see [`GameSave::analyze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlyAnalysis {
+    /// The index of the move in the game, starting at 0.
+    pub ply: usize,
+    /// The color of the player who played the move.
+    pub player: Disc,
+    /// The move.
+    pub mov: Move,
+    /// Evaluation of the position before the move, for `player`.
+    pub before: i16,
+    /// Evaluation of the position after the move, for `player`.
+    pub after: i16,
+}
+
+impl PlyAnalysis {
+    /// Return how much the move changed the evaluation for the player who
+    /// played it, a large negative swing is a potential blunder.
+    #[must_use]
+    pub fn swing(&self) -> i16 {
+        self.after.saturating_sub(self.before)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSave {
+    /// Version of the format of the save, see [`GameSave::VERSION`]. The saves
+    /// made before it was stored are the version 0.
+    #[serde(default)]
+    pub version: u32,
+    /// Title of the save, will be showed in the replay command when selecting
+    pub title: String,
+    /// Black player's type
+    pub black_type: PlayerType,
+    /// White player's type
+    pub white_type: PlayerType,
+    /// Black player's name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub black_name: Option<Cow<'static, str>>,
+    /// White player's name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub white_name: Option<Cow<'static, str>>,
+    /// Moves during the game
+    pub moves: Vec<Move>,
+    /// The passes during the game, the forfeited turns and the forced passes,
+    /// as the number of moves played before each of them, see
+    /// [`GameSave::moves_with_passes`]. Empty in the saves made before the
+    /// passes were stored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passes: Vec<usize>,
+    /// The state of the Game at the end, should not be [`State::Playing`]
+    pub end_state: State,
+    /// How the scores of the end state were counted
+    #[serde(default)]
+    pub scoring_rule: ScoringRule,
+    /// Who played the first move, the saves made before it was stored
+    /// started with black.
+    #[serde(default = "GameSave::default_first_player")]
+    pub first_player: Disc,
+    /// The board before the first move, stored in the notation of
+    /// [`Board::from_str`]. `None` is the standard starting position, see
+    /// [`GameSave::start_board`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "GameSave::serialize_start")]
+    #[serde(deserialize_with = "GameSave::deserialize_start")]
+    pub start: Option<Board>,
+    /// Name of the file of the save without its extension, the title is used
+    /// if it's `None`. It isn't stored in the save.
+    #[serde(skip)]
+    pub file_stem: Option<String>,
+}
+
+impl GameSave {
+    /// The version of the saves written by this version of 0the. The version
+    /// 1 stores the empty squares of [`State::Winned`], they are counted
+    /// again when an older save is read.
+    pub const VERSION: u32 = 1;
+
+    pub fn new(title: String, black: &dyn Player, white: &dyn Player) -> GameSave {
+        GameSave {
+            version: GameSave::VERSION,
+            title,
+            black_type: black.player_type(),
+            white_type: white.player_type(),
+            black_name: black.name(),
+            white_name: white.name(),
+            moves: Vec::new(),
+            passes: Vec::new(),
+            end_state: State::Playing,
+            scoring_rule: ScoringRule::default(),
+            first_player: Disc::Black,
+            start: None,
+            file_stem: None,
+        }
+    }
+
+    fn default_first_player() -> Disc {
+        Disc::Black
+    }
+
+    fn serialize_start<S: Serializer>(
+        start: &Option<Board>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match start {
+            Some(board) => serializer.serialize_some(&board.to_notation()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize_start<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Board>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|notation| Board::from_str(&notation))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+
+    /// Return the board before the first move of the game, the standard
+    /// starting position if the save doesn't store one.
+    #[must_use]
+    pub fn start_board(&self) -> Board {
+        self.start.clone().unwrap_or_default()
+    }
+
+    /// Return `true` if the game started from the standard starting position
+    /// with Black first, the only games the openings apply to.
+    pub fn has_standard_start(&self) -> bool {
+        self.first_player == Disc::Black && self.start_board() == Board::new()
+    }
+
+    /// Return the file stem of a save created at `time`, e.g:
+    /// `20240817-143005-123456789`. It is safe in a file name on every
+    /// platform and the stems sort in the chronological order.
+    pub fn file_stem_at(time: DateTime<Utc>) -> String {
+        time.format("%Y%m%d-%H%M%S-%9f").to_string()
+    }
+
+    /// Return the name of the file where the save is stored, it is the
+    /// [`file_stem`], or the title if there is none, with every character
+    /// that is not safe in a file name replaced by an underscore, followed by
+    /// the extension of the `format`.
+    ///
+    /// [`file_stem`]: GameSave::file_stem
+    pub fn file_name(&self, format: SaveFormat) -> String {
+        let mut name: String = self
+            .file_stem
+            .as_deref()
+            .unwrap_or(&self.title)
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || "-_.+ ".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        // don't create hidden files or `.` / `..` paths
+        if name.is_empty() || name.starts_with('.') {
+            name.insert(0, '_');
+        }
+
+        name.push('.');
+        name.push_str(format.extension());
+        name
+    }
+
+    /// Return the moves of the game in order with its passes, `None` is a
+    /// pass.
+    #[must_use]
+    pub fn moves_with_passes(&self) -> Vec<Option<Move>> {
+        moves_with_passes(&self.moves, &self.passes)
+    }
+
+    /// Build the save of a game from its moves written one after the other in
+    /// the algebric notation, e.g: `f5d6c3d3`, the spaces are ignored. A
+    /// transcript doesn't tell who played, the players are humans named
+    /// `Unknown`. The game is aborted if it isn't over.
+    pub fn from_transcript(title: String, transcript: &str) -> Result<GameSave> {
+        let chars: Vec<char> = transcript
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let moves = chars
+            .chunks(2)
+            .map(|mov| Move::from_algebric(&mov.iter().collect::<String>()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let unknown = Some(Cow::Borrowed("Unknown"));
+        GameSave::from_moves(title, moves, unknown.clone(), unknown)
+    }
+
+    /// Build the save of a game between two humans from its `moves`, played
+    /// from the standard starting position by Black first. The moves are
+    /// replayed to check they are legal and to find the passes and the end
+    /// state, the game is aborted if it isn't over.
+    pub(crate) fn from_moves(
+        title: String,
+        moves: Vec<Move>,
+        black_name: Option<Cow<'static, str>>,
+        white_name: Option<Cow<'static, str>>,
+    ) -> Result<GameSave> {
+        let mut board = Board::new();
+        let mut turn = Disc::Black;
+        let mut passes = Vec::new();
+        for (index, &mov) in moves.iter().enumerate() {
+            if board.legal_moves(turn) == 0 {
+                passes.push(index);
+            }
+            turn = board
+                .play_sequence(turn, &[mov])
+                .map_err(|_| OthelloError::IllegalMoveInSequence { index, mov })?;
+        }
+
+        let end_state = if board.is_terminal() {
+            board.end_state(
+                ScoringRule::ChampionshipWinnerTakesEmpties,
+                |winner_color| {
+                    let name = match winner_color {
+                        Disc::White => &white_name,
+                        Disc::Black => &black_name,
+                        Disc::Empty => unreachable!(),
+                    };
+                    name.as_deref()
+                        .map_or_else(|| winner_color.to_string(), str::to_string)
+                },
+            )
+        } else {
+            State::Aborted
+        };
+
+        Ok(GameSave {
+            version: GameSave::VERSION,
+            title,
+            black_type: PlayerType::Human,
+            white_type: PlayerType::Human,
+            black_name,
+            white_name,
+            moves,
+            passes,
+            end_state,
+            scoring_rule: ScoringRule::ChampionshipWinnerTakesEmpties,
+            first_player: Disc::Black,
+            start: None,
+            file_stem: None,
+        })
+    }
+
+    pub fn push_move(&mut self, movemnt: Move) {
+        self.moves.push(movemnt);
+    }
+
+    /// Push the move if it is legal after the moves of the save, replayed
+    /// from its starting position. The whole game is replayed, use
+    /// [`GameSave::try_push_move_on`] if the position is known.
+    pub fn try_push_move(&mut self, mov: Move) -> Result<()> {
+        let mut board = self.start_board();
+        let (_, mut turn) = board.play_with_passes(self.first_player, &self.moves_with_passes())?;
+        if board.legal_moves(turn) == 0 {
+            // the player can't play, his turn is forfeited
+            turn = !turn;
+        }
+        self.try_push_move_on(&board, turn, mov)
+    }
+
+    /// Push the move if it is legal for `player` on the `board`, the position
+    /// after the moves of the save.
+    pub fn try_push_move_on(&mut self, board: &Board, player: Disc, mov: Move) -> Result<()> {
+        if !board.is_legal(player, mov) {
+            return Err(OthelloError::IllegalMove {
+                row: mov.row,
+                col: mov.col,
+            });
+        }
+
+        self.moves.push(mov);
+        Ok(())
+    }
+
+    pub fn set_end_state(&mut self, state: State) {
+        assert_ne!(state, State::Playing);
+        self.end_state = state;
+    }
+
+    /// Iterate over the moves of the game with the discs they flipped and the
+    /// board after them, e.g: to animate the flips when replaying.
+    pub fn positions(&self) -> Positions {
+        Positions {
+            entries: self.moves_with_passes().into_iter(),
+            board: self.start_board(),
+            turn: self.first_player,
+        }
+    }
+
+    /// Evaluate the position before and after each move of the game,
+    /// `evaluator` returns the evaluation of the board for the player of the
+    /// given color, higher is better.
+    pub fn analyze(&self, evaluator: &dyn Fn(&Board, Disc) -> i16) -> Vec<PlyAnalysis> {
+        let mut before = self.start_board();
+
+        self.positions()
+            .enumerate()
+            .map(|(ply, played)| {
+                let analysis = PlyAnalysis {
+                    ply,
+                    player: played.player,
+                    mov: played.mov,
+                    before: evaluator(&before, played.player),
+                    after: evaluator(&played.board, played.player),
+                };
+                before = played.board;
+                analysis
+            })
+            .collect()
+    }
+
+    /// Render the game as text frames, e.g: to play it back as an animation.
+    /// The first frame is the starting position and there is one frame after
+    /// each move, drawn by [`Board::to_ascii_art`] below a caption with the
+    /// move just played.
+    ///
+    /// It fails with the index of the first illegal move.
+    pub fn to_frames(&self) -> Result<Vec<String>> {
+        let start = self.start_board();
+        start
+            .clone()
+            .play_with_passes(self.first_player, &self.moves_with_passes())?;
+
+        let border = BorderStyle::default();
+        let mut frames = Vec::with_capacity(self.moves.len() + 1);
+        frames.push(format!("Start\n{}", start.to_ascii_art(border)));
+        for (ply, played) in self.positions().enumerate() {
+            frames.push(format!(
+                "Move {}: {} plays {}\n{}",
+                ply + 1,
+                played.player,
+                played.mov.to_algebric(),
+                played.board.to_ascii_art(border)
             ));
         }
 
-        game
+        Ok(frames)
+    }
+
+    /// Write the save in the `format` as a new file named [`file_name`] in
+    /// the directory `dir`, it is created if it doesn't exist. Return the
+    /// path of the file.
+    ///
+    /// It fails if there is already a save with the same file name.
+    ///
+    /// [`file_name`]: GameSave::file_name
+    pub fn write_to(&self, dir: &Path, format: SaveFormat) -> Result<PathBuf> {
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let filepath = dir.join(self.file_name(format));
+        let file = File::create_new(&filepath)?;
+
+        let res = self.write_file(file, format);
+        if res.is_err() {
+            // don't leave a truncated save, it couldn't be read by the replay
+            // command.
+            let _ = fs::remove_file(&filepath);
+        }
+        res.map(|()| filepath)
+    }
+
+    /// Write the whole save into the `file` and wait until it is on the disk.
+    fn write_file(&self, file: File, format: SaveFormat) -> Result<()> {
+        let mut writer = BufWriter::new(file);
+
+        self.write_as(&mut writer, format)?;
+        // write a new line otherwise on unix platform it may not be super
+        // happy.
+        writer.write_all(b"\n")?;
+
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Path of the save of the game while it's being played, in the directory
+    /// `dir`. It doesn't end with the extension of the `format` so it's not
+    /// listed with the finished games.
+    pub fn in_progress_path(&self, dir: &Path, format: SaveFormat) -> PathBuf {
+        dir.join(format!("{}.part", self.file_name(format)))
+    }
+
+    /// Write the save of a game that is being played in the directory `dir`,
+    /// replacing the previous one, see [`in_progress_path`].
+    ///
+    /// [`in_progress_path`]: GameSave::in_progress_path
+    pub fn write_in_progress(&self, dir: &Path, format: SaveFormat) -> Result<()> {
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+
+        self.write_file(File::create(self.in_progress_path(dir, format))?, format)
+    }
+
+    /// Compare the moves of this game with the moves of `other` and return the
+    /// first difference between the two, or `None` if they played exactly the
+    /// same moves.
+    ///
+    /// If one game is a prefix of the other, the difference is at the end of
+    /// the shortest game and its move is `None`.
+    pub fn diff(&self, other: &GameSave) -> Option<MovesDiff> {
+        let len = self.moves.len().max(other.moves.len());
+        (0..len)
+            .map(|ply| MovesDiff {
+                ply,
+                left: self.moves.get(ply).copied(),
+                right: other.moves.get(ply).copied(),
+            })
+            .find(|diff| diff.left != diff.right)
+    }
+
+    /// Serializes the struct into a pretty json string, with spaces and
+    /// newlines.
+    #[inline]
+    #[track_caller]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Serializes the struct into a compact json string, the format of the
+    /// save files.
+    #[inline]
+    #[track_caller]
+    pub fn to_json_compact(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    #[inline]
+    pub fn from_json(data: &str) -> Result<GameSave, serde_json::Error> {
+        serde_json::from_str(data).map(GameSave::upgrade)
+    }
+
+    /// Serializes the struct as json into the `writer`, with spaces and
+    /// newlines if `pretty` is true, compact otherwise.
+    pub fn to_writer(&self, writer: impl Write, pretty: bool) -> Result<()> {
+        if pretty {
+            serde_json::to_writer_pretty(writer, self)?;
+        } else {
+            serde_json::to_writer(writer, self)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a save from the json read from the `reader`.
+    pub fn from_reader(reader: impl Read) -> Result<GameSave> {
+        Ok(serde_json::from_reader(reader).map(GameSave::upgrade)?)
+    }
+
+    /// Serializes the struct into the `writer` in the `format` of the save
+    /// files, compact json or pretty RON.
+    pub fn write_as(&self, writer: impl Write, format: SaveFormat) -> Result<()> {
+        match format {
+            SaveFormat::Json => self.to_writer(writer, false),
+            SaveFormat::Ron => {
+                ron::ser::to_writer_pretty(writer, self, ron::ser::PrettyConfig::default())?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Deserializes a save in the `format` read from the `reader`.
+    pub fn read_as(reader: impl Read, format: SaveFormat) -> Result<GameSave> {
+        match format {
+            SaveFormat::Json => GameSave::from_reader(reader),
+            SaveFormat::Ron => Ok(ron::de::from_reader(reader).map(GameSave::upgrade)?),
+        }
+    }
+
+    /// Upgrade a save read in an older version to [`GameSave::VERSION`].
+    fn upgrade(mut self) -> GameSave {
+        if self.version == 0 {
+            // the empty squares default to zero, they are counted on the board
+            // at the end of the game. A save that can't be replayed is kept
+            // as is, `verify` tells why.
+            if let Ok((
+                _,
+                State::Winned {
+                    empty_squares: replayed,
+                    ..
+                },
+            )) = self.replay_silently()
+            {
+                if let State::Winned { empty_squares, .. } = &mut self.end_state {
+                    *empty_squares = replayed;
+                }
+            }
+        }
+        self.version = GameSave::VERSION;
+        self
+    }
+
+    /// Renders the summary shown at the end of a replay, `board` is the board
+    /// at the end of the game.
+    fn render_summary(&self, s: &mut impl WriteColor, board: &Board) -> Result<()> {
+        writeln!(s)?;
+        s.set_color(&style::WHITE_BOLD)?;
+        writeln!(s, "Summary of {:?}", self.title)?;
+        s.reset()?;
+
+        board.render(s, None, RenderStyle::default())?;
+
+        writeln!(s, "  {}", self.end_state)?;
+        writeln!(s, "  Moves played: {}", self.moves.len())?;
+        // the openings are only known from the standard starting position
+        let opening = self
+            .has_standard_start()
+            .then(|| opening::opening_name(&self.moves))
+            .flatten();
+        if let Some(name) = opening {
+            writeln!(s, "  Opening: {name}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Interactively replay a game, the replay is silent if the standard
+    /// input isn't a terminal, see [`GameSave::replay_with`].
+    pub fn replay(&mut self, stream: StandardStream) -> Result<()> {
+        self.replay_with(stream, io::stdin().is_terminal())
+    }
+
+    /// Replay a game, if `interactive` the board is rendered after each move
+    /// and the replay waits for a key press. Otherwise the moves are replayed
+    /// without reading the standard input and only the summary is rendered.
+    ///
+    /// The save is checked by [`GameSave::verify`] before replaying anything,
+    /// it fails with the same errors. It also fails with
+    /// [`OthelloError::ReplayMismatch`] if the replayed game doesn't end like
+    /// the recorded one.
+    pub fn replay_with(&mut self, stream: StandardStream, interactive: bool) -> Result<()> {
+        // the players replay the moves and the passes one after the other
+        let (entries, game_state) = self.replay_silently()?;
+        self.check_end_state(game_state)?;
+        let entries = Arc::new(entries);
+
+        let black_player = ReplayPlayer {
+            entries: entries.clone(),
+            color: Disc::Black,
+            player_type: self.black_type,
+            name: self.black_name.clone(),
+            end_state: self.end_state.clone(),
+            interactive,
+        };
+        let white_player = ReplayPlayer {
+            entries,
+            color: Disc::White,
+            player_type: self.white_type,
+            name: self.white_name.clone(),
+            end_state: self.end_state.clone(),
+            interactive,
+        };
+
+        let mut game = Game::with_board(
+            self.start_board(),
+            Box::new(white_player),
+            Box::new(black_player),
+            stream,
+            GameSettings {
+                legal_move_hints: LegalMoveHints::All,
+                legal_move_marker: RenderStyle::Unicode.legal_move_marker(),
+                saves_game_dir: None,
+                game_record: false,
+                render: interactive,
+                incremental_render: false,
+                allow_forced_pass: true,
+                autosave: false,
+                autosave_throttle: AutosaveThrottle::default(),
+                save_format: SaveFormat::default(),
+                scoring_rule: self.scoring_rule,
+                resign_threshold: None,
+                check_repeated_positions: cfg!(debug_assertions),
+                first_player: self.first_player,
+                time_control: None,
+                interrupt: None,
+                messages: Messages::default(),
+            },
+        )?;
+
+        game.play()?;
+        let game_state = game.state.clone();
+        self.render_summary(&mut *game.stream.borrow_mut(), &game.board)?;
+        game.post_play()?;
+
+        // check the replay in fact works and get the same result as recorded
+        self.check_end_state(game_state)
+    }
+
+    /// Replay the moves without rendering anything and check the game ends
+    /// like it was recorded. It fails with
+    /// [`OthelloError::IllegalMoveInSequence`] at the first illegal move and
+    /// with [`OthelloError::ReplayMismatch`] if the game doesn't end like it
+    /// was recorded, e.g: the save is corrupted or the rules changed since.
+    pub fn verify(&self) -> Result<()> {
+        let (_, game_state) = self.replay_silently()?;
+        self.check_end_state(game_state)
+    }
+
+    /// Replay the moves and the passes of the game from its starting
+    /// position, the forfeited turns missing from the old saves are inferred.
+    /// Return the moves with all the passes, `None` is a pass, and the state
+    /// at the end of the replay.
+    ///
+    /// It fails with [`OthelloError::IllegalMoveInSequence`] at the first
+    /// illegal move.
+    fn replay_silently(&self) -> Result<(Vec<Option<Move>>, State)> {
+        let mut board = self.start_board();
+        let (entries, mut turn) =
+            board.play_with_passes(self.first_player, &self.moves_with_passes())?;
+        if board.legal_moves(turn) == 0 {
+            // the player can't play, his turn is forfeited
+            turn = !turn;
+        }
+
+        let game_state =
+            if board.legal_moves(Disc::Black) == 0 && board.legal_moves(Disc::White) == 0 {
+                board.end_state(self.scoring_rule, |winner_color| {
+                    let name = match winner_color {
+                        Disc::White => &self.white_name,
+                        Disc::Black => &self.black_name,
+                        Disc::Empty => unreachable!(),
+                    };
+                    name.as_deref()
+                        .map_or_else(|| winner_color.to_string(), str::to_string)
+                })
+            } else {
+                match self.end_state {
+                    // the game was left before its end, the moves can't tell it
+                    State::Draw | State::Aborted => self.end_state.clone(),
+                    // only the player to move can run out of time
+                    State::TimedOut { loser } if loser == turn => self.end_state.clone(),
+                    _ => State::Aborted,
+                }
+            };
+
+        Ok((entries, game_state))
+    }
+
+    /// Check that a replay of this game ended in `game_state` like the
+    /// recorded game.
+    fn check_end_state(&self, game_state: State) -> Result<()> {
+        if game_state != self.end_state {
+            return Err(OthelloError::ReplayMismatch {
+                expected: self.end_state.clone(),
+                got: game_state,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Which legal moves are dotted on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegalMoveHints {
+    /// No legal move is dotted.
+    None,
+    /// All the legal moves are dotted.
+    #[default]
+    All,
+    /// Only the best move found by a shallow search is dotted.
+    BestOnly,
+    /// Only the legal moves on the corners are dotted.
+    CornersOnly,
+}
+
+impl LegalMoveHints {
+    /// Depth of the search of the best move of [`LegalMoveHints::BestOnly`].
+    const BEST_MOVE_DEPTH: SearchDepth = SearchDepth::Plies(2);
+
+    /// Return the moves to dot among the `legal_moves` of `player` on the
+    /// `board`, `None` if none is dotted.
+    #[must_use]
+    pub fn hinted_moves(self, board: &Board, player: Disc, legal_moves: u64) -> Option<u64> {
+        match self {
+            LegalMoveHints::None => None,
+            LegalMoveHints::All => Some(legal_moves),
+            LegalMoveHints::BestOnly => search::best_move(board, player, Self::BEST_MOVE_DEPTH)
+                .map(|mov| 1 << mov.into_idx()),
+            LegalMoveHints::CornersOnly => Some(legal_moves & Board::CORNERS),
+        }
+    }
+}
+
+/// How the scores are counted at the end of the game, the winner is the same
+/// with both rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScoringRule {
+    /// The rule of the World Othello Federation, the empty squares are given
+    /// to the winner.
+    #[default]
+    #[serde(alias = "Championship")]
+    ChampionshipWinnerTakesEmpties,
+    /// Only the discs on the board are counted.
+    #[serde(alias = "DiscCount")]
+    DiscCountOnly,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSettings {
+    /// Which legal moves are dotted on the board.
+    ///
+    /// # Default
+    ///
+    /// [`LegalMoveHints::All`]
+    pub legal_move_hints: LegalMoveHints,
+    /// The character drawn on the squares of the legal moves, use
+    /// [`set_legal_move_marker`] to check it is valid.
+    ///
+    /// # Default
+    ///
+    /// `•`
+    ///
+    /// [`set_legal_move_marker`]: GameSettings::set_legal_move_marker
+    pub legal_move_marker: char,
+    /// Where we save the games, if the directory doesn't exists 0the will
+    /// create it.
+    ///
+    /// # Default
+    ///
+    /// [`DEFAULT_GAME_SAVES_DIR`][struct@crate::DEFAULT_GAME_SAVES_DIR]
+    pub saves_game_dir: Option<PathBuf>,
+    /// Do we save the games?
+    ///
+    /// # Default
+    ///
+    /// `true`
+    pub game_record: bool,
+    /// Do we render the board? When disabled the board is never drawn, even
+    /// for human players, useful to play a lot of games between bots quickly.
+    ///
+    /// # Default
+    ///
+    /// `true`
+    pub render: bool,
+    /// Do we only redraw the squares that changed? The board is drawn at the
+    /// top of the terminal and updated in place with the ANSI cursor
+    /// addressing, the terminal must support it.
+    ///
+    /// # Default
+    ///
+    /// `false`
+    pub incremental_render: bool,
+    /// Can the players pass their turn with [`Game::force_pass`] even if they
+    /// can move? It is against the rules, only useful to analyze positions.
+    ///
+    /// # Default
+    ///
+    /// `false`
+    pub allow_forced_pass: bool,
+    /// Do we write the save after every move? So the game isn't lost if 0the
+    /// crashes, only used if the games are recorded.
+    ///
+    /// # Default
+    ///
+    /// `true`
+    pub autosave: bool,
+    /// How often the game is written by the autosave, e.g: not after every
+    /// move in the fast games between bots.
+    ///
+    /// # Default
+    ///
+    /// `AutosaveThrottle::Moves(1)`, after every move.
+    pub autosave_throttle: AutosaveThrottle,
+    /// The format of the files of the saves.
+    ///
+    /// # Default
+    ///
+    /// [`SaveFormat::Json`]
+    pub save_format: SaveFormat,
+    /// How the scores are counted at the end of the game.
+    ///
+    /// # Default
+    ///
+    /// [`ScoringRule::ChampionshipWinnerTakesEmpties`]
+    pub scoring_rule: ScoringRule,
+    /// Suggest the human players to resign when the evaluation of the board
+    /// is below minus this threshold for them, see
+    /// [`Game::should_suggest_resign`]. Resigning aborts the game.
+    ///
+    /// # Default
+    ///
+    /// `None`, the resignation is never suggested.
+    pub resign_threshold: Option<i16>,
+    /// Check that no position is reached twice during the game, it can't
+    /// happen because every move adds a disc, so it would be a bug in the
+    /// application of the moves. The game fails with
+    /// [`OthelloError::RepeatedPosition`] if it happens.
+    ///
+    /// # Default
+    ///
+    /// `true` in debug builds, `false` otherwise.
+    pub check_repeated_positions: bool,
+    /// Who plays the first move, it can't be [`Disc::Empty`].
+    ///
+    /// # Default
+    ///
+    /// [`Disc::Black`], like in the official rules.
+    pub first_player: Disc,
+    /// The time each player has to play all his moves, the player whose clock
+    /// runs out loses the game.
+    ///
+    /// # Default
+    ///
+    /// `None`, no clocks.
+    pub time_control: Option<TimeControl>,
+    /// Abort the game when the flag is set, e.g: by a Ctrl-C handler. The
+    /// game is still saved by [`Game::post_play`].
+    ///
+    /// # Default
+    ///
+    /// `None`, the game can't be interrupted.
+    pub interrupt: Option<InterruptFlag>,
+    /// The prompts shown to the players.
+    ///
+    /// # Default
+    ///
+    /// The English [`Messages`].
+    pub messages: Messages,
+}
+
+impl GameSettings {
+    /// Change the character drawn on the squares of the legal moves, it must
+    /// be a visible character as wide as one column of the terminal.
+    pub fn set_legal_move_marker(&mut self, marker: char) -> Result<()> {
+        if !style::is_single_width(marker) {
+            return Err(OthelloError::InvalidMarker(marker));
+        }
+        self.legal_move_marker = marker;
+        Ok(())
+    }
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        GameSettings {
+            legal_move_hints: LegalMoveHints::All,
+            legal_move_marker: RenderStyle::Unicode.legal_move_marker(),
+            saves_game_dir: DEFAULT_GAME_SAVES_DIR.clone(),
+            game_record: true,
+            render: true,
+            incremental_render: false,
+            allow_forced_pass: false,
+            autosave: true,
+            autosave_throttle: AutosaveThrottle::default(),
+            save_format: SaveFormat::default(),
+            scoring_rule: ScoringRule::default(),
+            resign_threshold: None,
+            check_repeated_positions: cfg!(debug_assertions),
+            first_player: Disc::Black,
+            time_control: None,
+            interrupt: None,
+            messages: Messages::default(),
+        }
+    }
+}
+
+/// How often the game in progress is written, see
+/// [`GameSettings::autosave_throttle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutosaveThrottle {
+    /// Write the game every this number of moves, `0` is like `1`.
+    Moves(u32),
+    /// Write the game at most once per this duration, after a move.
+    Interval(Duration),
+}
+
+impl Default for AutosaveThrottle {
+    fn default() -> Self {
+        AutosaveThrottle::Moves(1)
+    }
+}
+
+/// The format of the save files, see [`GameSettings::save_format`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveFormat {
+    /// Compact json, the saves are a single line.
+    #[default]
+    Json,
+    /// Pretty [RON](https://github.com/ron-rs/ron), easier to read and to
+    /// edit by hand.
+    Ron,
+}
+
+impl SaveFormat {
+    /// Extension of the save files in this format, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Json => "json",
+            SaveFormat::Ron => "ron",
+        }
+    }
+
+    /// Return the format of the save file at `path` from its extension, or
+    /// `None` if it's not the extension of a save.
+    pub fn from_path(path: &Path) -> Option<SaveFormat> {
+        match path.extension()?.to_str()? {
+            "json" => Some(SaveFormat::Json),
+            "ron" => Some(SaveFormat::Ron),
+            _ => None,
+        }
+    }
+}
+
+/// A flag shared with a signal handler to interrupt the games, see
+/// [`GameSettings::interrupt`]. The clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptFlag(Arc<AtomicBool>);
+
+impl InterruptFlag {
+    pub fn new() -> InterruptFlag {
+        InterruptFlag::default()
+    }
+
+    /// Set the flag, the game is aborted before the next turn.
+    pub fn interrupt(&self) {
+        self.0.store(true, AtomicOrdering::SeqCst);
+    }
+
+    /// Was the flag set since the last [`reset`]?
+    ///
+    /// [`reset`]: InterruptFlag::reset
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(AtomicOrdering::SeqCst)
+    }
+
+    /// Clear the flag, e.g: before starting a new game.
+    pub fn reset(&self) {
+        self.0.store(false, AtomicOrdering::SeqCst);
+    }
+}
+
+impl PartialEq for InterruptFlag {
+    /// Two flags are equal if they are clones of the same flag.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The time given to each player, e.g: 5 minutes with an increment of 3
+/// seconds.
+///
+/// The clock can't interrupt a player while he thinks, it is checked once he
+/// played: a player who took longer than the time left on his clock loses the
+/// game and his move isn't played. The game ends in [`State::TimedOut`] and the
+/// replay times the player out before the same move, see
+/// [`ThinkOutcome::TimedOut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Time on the clocks at the start of the game.
+    pub base: Duration,
+    /// Time added to the clock of a player after each of his moves.
+    pub increment: Duration,
+}
+
+/// Format the time left on a clock as `minutes:seconds`, e.g: `4:05`.
+fn format_clock(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Description of a square of the board, see [`Game::square_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquareInfo {
+    /// The algebric name of the square, e.g: `d3`.
+    pub algebric: String,
+    /// The disc on the square.
+    pub disc: Disc,
+    /// Is the square a legal move of the current player?
+    pub is_legal: bool,
+    /// Bitfield of the discs flipped if the current player plays on the
+    /// square, zero if it isn't a legal move.
+    pub flips: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum State {
+    /// The game is currently being played.
+    Playing,
+    /// One of the player winned the game.
+    Winned {
+        /// Who won the game?
+        winner_color: Disc,
+        /// What's his name?
+        winner_name: String,
+        /// Championship style score, the winner's score include empty squares
+        winner_score: u8,
+        /// Championship style score, the winner's score include empty squares
+        loser_score: u8,
+        /// Number of empty squares at the end of the game, they are included
+        /// in the winner's score. Always zero with the
+        /// [`ScoringRule::DiscCountOnly`] rule.
+        #[serde(default)]
+        empty_squares: u8,
+    },
+    /// The game ended in an equality of scores.
+    Draw,
+    /// The current player cannot play, his turn is forfeited (Rule no. 2)
+    TurnForfeited,
+    /// A player left the game before the end.
+    Aborted,
+    /// The clock of a player ran out, he lost the game, see
+    /// [`GameSettings::time_control`].
+    TimedOut {
+        /// Who ran out of time?
+        loser: Disc,
+    },
+}
+
+impl State {
+    /// Return the discs on the board at the end of the game, without the
+    /// empty squares given to the winner. The winner's discs are first and
+    /// the loser's discs second, `None` if no one won.
+    pub fn disc_counts(&self) -> Option<(u8, u8)> {
+        match self {
+            State::Winned {
+                winner_score,
+                loser_score,
+                empty_squares,
+                ..
+            } => Some((winner_score - empty_squares, *loser_score)),
+            _ => None,
+        }
+    }
+
+    /// Return the scores of the winner and the loser, e.g: `40-24`, followed
+    /// by the discs on the board if there were empty squares, e.g:
+    /// `40-24 (discs 33-24, +7 empties)`. `None` if no one won.
+    pub fn score_line(&self) -> Option<String> {
+        let State::Winned {
+            winner_score,
+            loser_score,
+            empty_squares,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let (winner_discs, loser_discs) = self.disc_counts()?;
+
+        Some(if *empty_squares == 0 {
+            format!("{winner_score}-{loser_score}")
+        } else {
+            format!(
+                "{winner_score}-{loser_score} (discs {winner_discs}-{loser_discs}, +{empty_squares} empties)"
+            )
+        })
+    }
+}
+
+impl Display for State {
+    /// Write the result of the game as a sentence, e.g:
+    /// `Alice (Black) wins with 40-24.`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            State::Playing => write!(f, "The game is being played."),
+            State::Winned {
+                winner_color,
+                winner_name,
+                ..
+            } => write!(
+                f,
+                "{winner_name} ({winner_color}) wins with {}.",
+                self.score_line().unwrap()
+            ),
+            State::Draw => write!(f, "The game ended in a draw."),
+            State::TurnForfeited => write!(f, "The turn is forfeited."),
+            State::Aborted => write!(f, "The game was aborted."),
+            State::TimedOut { loser } => write!(f, "{loser} ran out of time, {} wins.", !*loser),
+        }
+    }
+}
+
+/// Summary of a finished game, see [`Game::result`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    /// Who won the game? `None` for a draw or an aborted game.
+    pub winner: Option<Disc>,
+    /// Score of Black, the empty squares are included in the score of the
+    /// winner like in [`State::Winned`], otherwise it's his discs.
+    pub black_score: u8,
+    /// Score of White, counted like `black_score`.
+    pub white_score: u8,
+    /// The state the game ended with.
+    pub terminal: State,
+}
+
+/// Maximum width of the names of the players next to the board, in columns
+/// of the terminal.
+const MAX_NAME_WIDTH: usize = 16;
+
+// TODO: make an option to disable all writes and replace with events.
+#[derive(Debug)]
+pub struct Game {
+    /// Squares of the game
+    board: Board,
+    /// Black player
+    black_player: Box<dyn Player>,
+    /// White player
+    white_player: Box<dyn Player>,
+    /// Who's next turn?
+    ///
+    /// Note:
+    ///
+    /// `turn` cannot be `Disc::Empty`.
+    turn: Disc,
+    /// The legal moves of the current player (`turn` field).
+    current_legal_moves: Option<u64>,
+    /// The list of the legal moves of the current player, computed with
+    /// `current_legal_moves` and empty when they aren't.
+    legal_move_list: Vec<Move>,
+    /// The legal moves of the opponent, computed when the turn of the current
+    /// player is forfeited and reused once it's the opponent's turn.
+    forfeit_legal_moves: Option<u64>,
+    /// The stream, usualy stdout where we render the game.
+    stream: RefCell<StandardStream>,
+    /// The board and the legal moves drawn by the last render, only kept if
+    /// [`GameSettings::incremental_render`] is enabled.
+    rendered: RefCell<Option<(Board, Option<u64>)>>,
+    /// The state of the game
+    state: State,
+    /// Game settings
+    pub settings: GameSettings,
+    /// Game save should only be some if the settings has been enabled
+    save: Option<GameSave>,
+    /// The moves played since the start of the game
+    history: Vec<Move>,
+    /// The passes since the start of the game, as the number of moves of the
+    /// history played before each of them.
+    passes: Vec<usize>,
+    /// The length of the history and the time of the last autosave, or of
+    /// the creation of the game.
+    last_autosave: (usize, Instant),
+    /// The positions reached since the start of the game, only filled if
+    /// [`GameSettings::check_repeated_positions`] is enabled.
+    seen_positions: HashSet<Board>,
+    /// The board at the start of the game, used to rebuild the board from the
+    /// history
+    start: Board,
+    /// The time left to Black and White, if the game has a time control.
+    clocks: Option<(Duration, Duration)>,
+}
+
+impl Game {
+    /// The resignation isn't suggested before this move, the evaluation of
+    /// the opening is too unreliable, see [`Game::should_suggest_resign`].
+    pub const MIN_RESIGN_MOVE: u8 = 20;
+
+    /// Create a game from the standard starting position, see
+    /// [`Game::with_board`].
+    pub fn new(
+        white_player: Box<dyn Player>,
+        black_player: Box<dyn Player>,
+        stream: StandardStream,
+        settings: GameSettings,
+    ) -> Result<Game> {
+        Game::with_board(Board::new(), white_player, black_player, stream, settings)
+    }
+
+    /// Create a game starting from `board`.
+    ///
+    /// It fails with [`OthelloError::InvalidPlayerColor`] if
+    /// [`GameSettings::first_player`] is `Disc::Empty`.
+    pub fn with_board(
+        board: Board,
+        white_player: Box<dyn Player>,
+        black_player: Box<dyn Player>,
+        stream: StandardStream,
+        settings: GameSettings,
+    ) -> Result<Game> {
+        if settings.first_player == Disc::Empty {
+            return Err(OthelloError::InvalidPlayerColor(
+                settings.first_player.to_string(),
+            ));
+        }
+
+        let mut game = Game {
+            start: board.clone(),
+            clocks: settings
+                .time_control
+                .map(|control| (control.base, control.base)),
+            board,
+            white_player,
+            black_player,
+            turn: settings.first_player,
+            current_legal_moves: None,
+            legal_move_list: Vec::new(),
+            forfeit_legal_moves: None,
+            stream: RefCell::new(stream),
+            rendered: RefCell::new(None),
+            state: State::Playing,
+            settings,
+            save: None,
+            history: Vec::new(),
+            passes: Vec::new(),
+            last_autosave: (0, Instant::now()),
+            seen_positions: HashSet::new(),
+        };
+        if game.settings.check_repeated_positions {
+            game.seen_positions.insert(game.start.clone());
+        }
+
+        // player init
+        game.white_player.reset();
+        game.black_player.reset();
+        game.white_player.init_color(Disc::White);
+        game.black_player.init_color(Disc::Black);
+
+        // game save init
+        if game.settings.saves_game_dir.is_some() && game.settings.game_record {
+            let dt = Local::now();
+            let mut save = GameSave::new(
+                dt.to_rfc3339(),
+                game.black_player.as_ref(),
+                game.white_player.as_ref(),
+            );
+            save.scoring_rule = game.settings.scoring_rule;
+            save.first_player = game.settings.first_player;
+            if game.start != Board::new() {
+                save.start = Some(game.start.clone());
+            }
+            // without a custom title, the file is named after the time so the
+            // saves are listed in the order they were played.
+            save.file_stem = Some(GameSave::file_stem_at(dt.to_utc()));
+            game.save = Some(save);
+        }
+
+        Ok(game)
+    }
+
+    /// Create a game between a human and a `bot`, rendered to stdout with the
+    /// default settings. The human plays Black if `human_is_black`.
+    pub fn human_vs_bot(human_name: Option<String>, bot: BuiltinBot, human_is_black: bool) -> Game {
+        let human = Box::new(HumanPlayer::new(human_name));
+        let bot = bot.into_player();
+        let (white_player, black_player): (Box<dyn Player>, Box<dyn Player>) = if human_is_black {
+            (bot, human)
+        } else {
+            (human, bot)
+        };
+
+        // it's safe to unwrap, Black moves first with the default settings.
+        Game::new(
+            white_player,
+            black_player,
+            style::stdout(),
+            GameSettings::default(),
+        )
+        .unwrap()
+    }
+
+    /// Set the title of the game save, does nothing if the game isn't
+    /// recorded. The file of the save is named after the title, see
+    /// [`GameSave::file_name`]. Without a title, the title is the date and
+    /// time the game was created at and the file is named after the time.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        if let Some(save) = &mut self.save {
+            save.title = title.into();
+            save.file_stem = None;
+        }
+    }
+
+    /// Return the color of the player whose turn it is.
+    #[inline]
+    #[must_use]
+    pub fn turn(&self) -> Disc {
+        debug_assert_ne!(self.turn, Disc::Empty);
+        self.turn
+    }
+
+    fn is_legal(bitfield: u64, index: usize) -> bool {
+        (bitfield & (1 << index)) != 0
+    }
+
+    pub fn is_legal_move(&self, index: usize) -> Result<bool> {
+        let Some(moves) = self.current_legal_moves else {
+            return Err(OthelloError::LegalMovesNotComputed);
+        };
+        Ok(Self::is_legal(moves, index))
+    }
+
+    /// Describe the square at `mov` for the current player, the legal moves
+    /// must be computed.
+    pub fn square_info(&self, mov: Move) -> Result<SquareInfo> {
+        let is_legal = self.is_legal_move(mov.into_idx())?;
+        let flips = if is_legal {
+            self.board.move_outflanks(self.turn(), mov)
+        } else {
+            0
+        };
+
+        Ok(SquareInfo {
+            algebric: mov.to_algebric(),
+            disc: self.board.get_disc((mov.col, mov.row)),
+            is_legal,
+            flips,
+        })
+    }
+
+    fn make_turn(&mut self, mov @ Move { col, row }: Move) -> Result<()> {
+        // ensure the move is inside the legal moves.
+        let idx = (row * 8 + col) as u64;
+        if !self.is_legal_move(idx as usize)? {
+            return Err(OthelloError::IllegalMove { row, col });
+        }
+        // we store the move if we save the games, it is checked again on the
+        // board before the move.
+        if let Some(save) = &mut self.save {
+            save.try_push_move_on(&self.board, self.turn, mov)?;
+        }
+        #[cfg(debug_assertions)]
+        let scores_before = self.board.scores();
+
+        self.board.apply_move(self.turn, mov);
+        self.history.push(mov);
+        // the board changed, the legal moves must be computed again
+        self.forfeit_legal_moves = None;
+
+        #[cfg(debug_assertions)]
+        check_turn_invariant(scores_before, self.board.scores(), self.turn);
+
+        if self.settings.check_repeated_positions && !self.seen_positions.insert(self.board.clone())
+        {
+            return Err(OthelloError::RepeatedPosition {
+                ply: self.history.len(),
+            });
+        }
+
+        self.next_turn();
+
+        Ok(())
+    }
+
+    /// Pass the turn of the current player even if he can move, without
+    /// placing a disc, and compute the legal moves of the opponent. Only
+    /// allowed if [`GameSettings::allow_forced_pass`] is enabled.
+    ///
+    /// The pass is recorded in [`GameSave::passes`] and reproduced by the
+    /// replay. A player passes during [`Game::play`] with
+    /// [`ThinkOutcome::Pass`].
+    pub fn force_pass(&mut self) -> Result<()> {
+        if !self.settings.allow_forced_pass {
+            return Err(OthelloError::ForcedPassNotAllowed);
+        }
+
+        self.record_pass();
+        self.next_turn();
+        self.legal_moves();
+
+        Ok(())
+    }
+
+    /// Play the move written in the algebric notation, e.g: `d3`, for the
+    /// current player, the legal moves are computed if needed. If the turn of
+    /// the current player is forfeited, it is passed first like in
+    /// [`Game::play`].
+    ///
+    /// It fails with [`OthelloError::InvalidAlgebric`] if the notation is
+    /// invalid and with [`OthelloError::IllegalMove`] if the move is illegal.
+    pub fn apply_algebraic(&mut self, notation: &str) -> Result<()> {
+        let mov = Move::from_algebric(notation)?;
+
+        if self.current_legal_moves.is_none() {
+            self.legal_moves();
+        }
+        if self.state == State::TurnForfeited {
+            self.record_pass();
+            self.next_turn();
+            self.legal_moves();
+        }
+
+        self.make_turn(mov)
+    }
+
+    /// Record that the turn of the current player is passed, in the history
+    /// and in the save.
+    fn record_pass(&mut self) {
+        self.passes.push(self.history.len());
+        if let Some(save) = &mut self.save {
+            save.passes.push(self.history.len());
+        }
+    }
+
+    fn next_turn(&mut self) {
+        // Change the turn to the opponent
+        self.turn = !self.turn;
+        // Reset the current legal moves to `None`, just a simple safety used
+        // not to confuse between Black's and White's legal moves
+        self.current_legal_moves = None;
+        self.legal_move_list.clear();
+        // Set the state to playing, the player before could of had a State of
+        // `TurnForfeited` and if the other player can play it must not forfeit
+        // is turn
+        self.state = State::Playing;
+    }
+
+    /// Start the game of Othello between the two players
+    pub fn play(&mut self) -> Result<()> {
+        loop {
+            if self.abort_if_interrupted()? {
+                break;
+            }
+
+            self.legal_moves();
+            if self.settings.render && self.current_player().render_board() {
+                self.render(None)?;
+            }
+
+            match &self.state {
+                State::Playing => {}
+                state @ State::Winned { .. } => {
+                    let s = &mut *self.stream.borrow_mut();
+                    writeln!(s)?;
+                    writeln!(s, "  Congratulation! {state}")?;
+                    break;
+                }
+                state @ State::Draw => {
+                    let s = &mut *self.stream.borrow_mut();
+                    writeln!(s)?;
+                    writeln!(s, "  {state}")?;
+                    break;
+                }
+                State::TurnForfeited => {
+                    // the current player can't play so we pass the turn to the
+                    // opponent that can play.
+                    {
+                        let s = &mut *self.stream.borrow_mut();
+                        writeln!(
+                            s,
+                            "The turn of {} has been forfeited, he cannot play.",
+                            self.turn()
+                        )?;
+                    }
+                    self.record_pass();
+                    self.next_turn();
+                    continue;
+                }
+                State::Aborted | State::TimedOut { .. } => {
+                    unreachable!("the game stops as soon as it is aborted")
+                }
+            }
+
+            let think_start = Instant::now();
+            let mut previous_err = None;
+            let outcome = loop {
+                match self.player_think(previous_err.take()) {
+                    Ok(ThinkOutcome::OfferDraw) if !self.opponent().accept_draw(self)? => {
+                        let s = &mut *self.stream.borrow_mut();
+                        writeln!(s, "The draw has been declined.")?;
+                    }
+                    Ok(ThinkOutcome::Pass) if !self.settings.allow_forced_pass => {
+                        // a bot would most likely pass again
+                        if self.current_player().player_type() == PlayerType::Bot {
+                            return Err(OthelloError::ForcedPassNotAllowed);
+                        }
+                        previous_err = Some(OthelloError::ForcedPassNotAllowed);
+                    }
+                    Ok(outcome) => break outcome,
+                    // TODO: we may only recall `think` if the error is not an io error.
+                    Err(e) => previous_err = Some(e),
+                }
+            };
+            // `None` is a forced pass
+            let mov = match outcome {
+                ThinkOutcome::Move(mov) => Some(mov),
+                ThinkOutcome::Pass => None,
+                ThinkOutcome::TimedOut => {
+                    self.state = State::TimedOut { loser: self.turn() };
+                    let s = &mut *self.stream.borrow_mut();
+                    writeln!(s)?;
+                    writeln!(s, "  {}", self.state)?;
+                    break;
+                }
+                ThinkOutcome::Quit | ThinkOutcome::OfferDraw => {
+                    let s = &mut *self.stream.borrow_mut();
+                    writeln!(s)?;
+                    if outcome == ThinkOutcome::Quit {
+                        self.state = State::Aborted;
+                        writeln!(s, "  {}", self.state)?;
+                    } else {
+                        self.state = State::Draw;
+                        writeln!(s, "  Both players agreed to a draw.")?;
+                    }
+                    break;
+                }
+            };
+            // the move of a bot that was thinking during the interrupt isn't
+            // played
+            if self.abort_if_interrupted()? {
+                break;
+            }
+
+            // the clock can't stop a player while he thinks, it is checked
+            // once he played.
+            let player = self.turn();
+            if !self.spend_time(player, think_start.elapsed()) {
+                let s = &mut *self.stream.borrow_mut();
+                writeln!(s)?;
+                writeln!(s, "  {}", self.state)?;
+                break;
+            }
+
+            let played = match mov {
+                Some(mov) => self.make_turn(mov),
+                None => {
+                    self.record_pass();
+                    self.next_turn();
+                    Ok(())
+                }
+            };
+            match played {
+                Ok(()) => {
+                    self.add_increment(player);
+                    self.autosave();
+                }
+                Err(OthelloError::IllegalMove { row, col })
+                    if self.current_player().player_type() == PlayerType::Bot =>
+                {
+                    // a bot would most likely play the same move again
+                    return Err(OthelloError::IllegalBotMove {
+                        mov: Move { col, row },
+                        legal_moves: self.legal_move_list.clone(),
+                    });
+                }
+                Err(e @ OthelloError::IllegalMove { .. }) => {
+                    let s = &mut *self.stream.borrow_mut();
+                    s.set_color(&style::ERROR)?;
+                    writeln!(s, "{e}")?;
+                    s.reset()?;
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(())
+    }
+
+    /// Abort the game if it was interrupted, see [`GameSettings::interrupt`].
+    /// Return `true` if it was.
+    fn abort_if_interrupted(&mut self) -> Result<bool> {
+        if !self
+            .settings
+            .interrupt
+            .as_ref()
+            .is_some_and(InterruptFlag::is_interrupted)
+        {
+            return Ok(false);
+        }
+
+        self.state = State::Aborted;
+        let s = &mut *self.stream.borrow_mut();
+        writeln!(s)?;
+        writeln!(s, "  {}", self.state)?;
+        Ok(true)
+    }
+
+    /// Post play, actions like storing the saved game. The path of the save
+    /// is printed and returned. If the save can't be written, a warning is
+    /// printed to stderr and the save is printed as json so the record of the
+    /// game isn't lost.
+    // TODO: try to make it the implementation of Drop
+    pub fn post_play(&mut self) -> Result<Option<PathBuf>> {
+        if let Some(mut save) = self.save.take() {
+            save.end_state = self.state.clone();
+
+            let saves_dir = self
+                .settings
+                .saves_game_dir
+                .clone()
+                .expect("HMMMM it should really really not be None this is an error.");
+
+            let path = match save.write_to(&saves_dir, self.settings.save_format) {
+                Ok(path) => path,
+                Err(e) => {
+                    // the game was played, don't lose its record because the
+                    // saves directory is unwritable, print it so it can be
+                    // saved by hand.
+                    eprintln!(
+                        "WARNING: the game couldn't be saved in {}: {e}",
+                        saves_dir.display()
+                    );
+                    let s = &mut *self.stream.borrow_mut();
+                    writeln!(s, "{}", save.to_json_compact())?;
+                    return Ok(None);
+                }
+            };
+            // the game is saved, the in progress save isn't needed anymore
+            match fs::remove_file(save.in_progress_path(&saves_dir, self.settings.save_format)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+
+            // the statistics are only a bonus, the game is already saved
+            let stats_path = saves_dir.join(PlayerStats::FILE_NAME);
+            let res = PlayerStats::load(&stats_path).and_then(|mut stats| {
+                stats.update(&save);
+                stats.save(&stats_path)
+            });
+            if let Err(e) = res {
+                eprintln!("WARNING: the player statistics couldn't be updated: {e}");
+            }
+
+            Game::render_save_path(&mut *self.stream.borrow_mut(), &path)?;
+            return Ok(Some(path));
+        }
+        Ok(None)
+    }
+
+    /// Renders the message telling where the game was saved.
+    fn render_save_path(s: &mut impl WriteColor, path: &Path) -> io::Result<()> {
+        s.set_color(&style::SUBDUED)?;
+        writeln!(s, "Game saved to {}", path.display())?;
+        s.reset()
+    }
+
+    /// Write the game in progress if the autosave is enabled and the
+    /// [`GameSettings::autosave_throttle`] allows it, the game is written
+    /// anyway at the end by [`Game::post_play`].
+    fn autosave(&mut self) {
+        let Some(save) = &mut self.save else {
+            return;
+        };
+        if !self.settings.autosave {
+            return;
+        }
+
+        let (ply, at) = self.last_autosave;
+        let due = match self.settings.autosave_throttle {
+            AutosaveThrottle::Moves(moves) => {
+                self.history.len().saturating_sub(ply) >= moves.max(1) as usize
+            }
+            AutosaveThrottle::Interval(interval) => at.elapsed() >= interval,
+        };
+        if !due {
+            return;
+        }
+
+        // it's safe to unwrap, the game is only saved if the directory is set.
+        let saves_dir = self.settings.saves_game_dir.as_ref().unwrap();
+        if let Err(e) = save.write_in_progress(saves_dir, self.settings.save_format) {
+            // keep playing, the game is saved at the end.
+            eprintln!(
+                "WARNING: autosave disabled, the game couldn't be saved in {}: {e}",
+                saves_dir.display()
+            );
+            self.settings.autosave = false;
+        }
+        self.last_autosave = (self.history.len(), Instant::now());
+    }
+
+    /// Call the method `think` on the current player.
+    fn player_think(&self, previous_err: Option<OthelloError>) -> Result<ThinkOutcome> {
+        match self.turn() {
+            Disc::Black => self.black_player.think(self, previous_err),
+            Disc::White => self.white_player.think(self, previous_err),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    /// Return the current position of the game.
+    #[inline]
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Return the time left on the clock of `player`, `None` if the game has
+    /// no time control.
+    #[must_use]
+    pub fn clock(&self, player: Disc) -> Option<Duration> {
+        let (black, white) = self.clocks?;
+        match player {
+            Disc::Black => Some(black),
+            Disc::White => Some(white),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    fn clock_mut(&mut self, player: Disc) -> Option<&mut Duration> {
+        let (black, white) = self.clocks.as_mut()?;
+        match player {
+            Disc::Black => Some(black),
+            Disc::White => Some(white),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    /// Take the time `elapsed` while thinking from the clock of `player`.
+    /// Return `false` if his clock ran out, the game is then
+    /// [`State::TimedOut`].
+    fn spend_time(&mut self, player: Disc, elapsed: Duration) -> bool {
+        let Some(clock) = self.clock_mut(player) else {
+            return true;
+        };
+        match clock.checked_sub(elapsed) {
+            Some(left) if !left.is_zero() => {
+                *clock = left;
+                true
+            }
+            _ => {
+                *clock = Duration::ZERO;
+                self.state = State::TimedOut { loser: player };
+                false
+            }
+        }
+    }
+
+    /// Add the increment of the time control to the clock of `player`, after
+    /// his move.
+    fn add_increment(&mut self, player: Disc) {
+        if let Some(control) = self.settings.time_control {
+            // it's safe to unwrap, the clocks are set with the time control.
+            *self.clock_mut(player).unwrap() += control.increment;
+        }
+    }
+
+    /// Return the state of the game, it is decided when the legal moves are
+    /// computed.
+    #[inline]
+    #[must_use]
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Return the summary of the game once it is over, `None` while it is
+    /// being played, a forfeited turn doesn't end the game.
+    #[must_use]
+    pub fn result(&self) -> Option<GameResult> {
+        let (winner, black_score, white_score) = match &self.state {
+            State::Playing | State::TurnForfeited => return None,
+            State::Winned {
+                winner_color,
+                winner_score,
+                loser_score,
+                ..
+            } => match winner_color {
+                Disc::Black => (Some(Disc::Black), *winner_score, *loser_score),
+                Disc::White => (Some(Disc::White), *loser_score, *winner_score),
+                Disc::Empty => unreachable!(),
+            },
+            State::Draw | State::Aborted | State::TimedOut { .. } => {
+                let (white, black, _) = self.board.scores();
+                let winner = match self.state {
+                    State::TimedOut { loser } => Some(!loser),
+                    _ => None,
+                };
+                (winner, black, white)
+            }
+        };
+
+        Some(GameResult {
+            winner,
+            black_score,
+            white_score,
+            terminal: self.state.clone(),
+        })
+    }
+
+    /// Return the moves played since the start of the game, in order, even if
+    /// the game isn't recorded. The forfeited turns aren't in the history,
+    /// see [`Game::passes`].
+    #[inline]
+    #[must_use]
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// Return the moves played since the start of the game, in order.
+    #[deprecated = "use `Game::history` instead"]
+    #[inline]
+    #[must_use]
+    pub fn move_history(&self) -> &[Move] {
+        self.history()
+    }
+
+    /// Return the passes since the start of the game, the forfeited turns and
+    /// the forced passes, as the number of moves of the history played before
+    /// each of them.
+    #[inline]
+    #[must_use]
+    pub fn passes(&self) -> &[usize] {
+        &self.passes
+    }
+
+    /// Return the number of the move being played, starting at 1. The
+    /// forfeited turns aren't counted.
+    #[inline]
+    #[must_use]
+    pub fn move_number(&self) -> u8 {
+        self.history.len() as u8 + 1
+    }
+
+    /// Should the current player be suggested to resign? He is if the
+    /// `evaluator` rates the board below `-threshold` for him, but never
+    /// before the move [`Game::MIN_RESIGN_MOVE`].
+    #[must_use]
+    pub fn should_suggest_resign(
+        &self,
+        evaluator: &dyn Fn(&Board, Disc) -> i16,
+        threshold: i16,
+    ) -> bool {
+        self.move_number() >= Game::MIN_RESIGN_MOVE
+            && evaluator(&self.board, self.turn()) < threshold.saturating_neg()
+    }
+
+    /// Take back the moves played after the first `ply` moves of the
+    /// history, the board is rebuilt from the starting position and the
+    /// passes are replayed. Going back to move 0 restores the starting
+    /// position with the first player to move.
+    pub fn undo_to(&mut self, ply: usize) -> Result<()> {
+        if ply > self.history.len() {
+            return Err(OthelloError::InvalidPly(ply));
+        }
+        self.history.truncate(ply);
+        self.passes.retain(|&pass| pass < ply);
+        if let Some(save) = &mut self.save {
+            save.moves.truncate(ply);
+            save.passes.retain(|&pass| pass < ply);
+        }
+        // the moves taken back were maybe autosaved, count the next autosave
+        // from the moves kept.
+        self.last_autosave.0 = self.last_autosave.0.min(ply);
+
+        self.board = self.start.clone();
+        self.turn = self.settings.first_player;
+        self.seen_positions.clear();
+        if self.settings.check_repeated_positions {
+            self.seen_positions.insert(self.start.clone());
+        }
+        for entry in moves_with_passes(&self.history, &self.passes) {
+            (_, self.turn) = self.board.play_with_passes(self.turn, &[entry])?;
+            if self.settings.check_repeated_positions {
+                self.seen_positions.insert(self.board.clone());
+            }
+        }
+        self.current_legal_moves = None;
+        self.legal_move_list.clear();
+        self.forfeit_legal_moves = None;
+        self.state = State::Playing;
+
+        Ok(())
+    }
+
+    /// Give back the players, white first, e.g: to play another game with
+    /// them, see [`Player::reset`].
+    pub fn into_players(self) -> (Box<dyn Player>, Box<dyn Player>) {
+        (self.white_player, self.black_player)
+    }
+
+    /// Return the opponent of the current player.
+    pub fn opponent(&self) -> &dyn Player {
+        match self.turn() {
+            Disc::White => self.black_player.as_ref(),
+            Disc::Black => self.white_player.as_ref(),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    pub fn current_player(&self) -> &dyn Player {
+        match self.turn() {
+            Disc::White => self.white_player.as_ref(),
+            Disc::Black => self.black_player.as_ref(),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn white_name(&self) -> Cow<'_, str> {
+        self.white_player.force_name()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn black_name(&self) -> Cow<'_, str> {
+        self.black_player.force_name()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn player_name(&self) -> Cow<'_, str> {
+        match self.turn {
+            Disc::White => self.white_name(),
+            Disc::Black => self.black_name(),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn maybe_name(&self) -> Option<Cow<'static, str>> {
+        match self.turn {
+            Disc::White => self.white_player.name(),
+            Disc::Black => self.black_player.name(),
+            Disc::Empty => unreachable!(),
+        }
+    }
+
+    /// Renders the board game to stdout. With
+    /// [`GameSettings::incremental_render`] the board is drawn at the top of
+    /// the terminal the first time, then only the changed squares, the scores
+    /// and the clocks are redrawn and the text below the board is cleared.
+    pub fn render(&self, s: Option<&mut StandardStream>) -> Result<()> {
+        let mut _s = self.stream.borrow_mut();
+        let s: &mut StandardStream = s.unwrap_or(&mut *_s);
+        let Some(legal_moves) = self.current_legal_moves else {
+            return Err(OthelloError::LegalMovesNotComputed);
+        };
+
+        let legal_moves =
+            self.settings
+                .legal_move_hints
+                .hinted_moves(&self.board, self.turn(), legal_moves);
+
+        if !self.settings.incremental_render {
+            return self.render_full(s, legal_moves);
+        }
+
+        let previous = self
+            .rendered
+            .replace(Some((self.board.clone(), legal_moves)));
+        let Some((previous, previous_legal_moves)) = previous else {
+            // clear the screen, the board is drawn at the top
+            write!(s, "\x1b[H\x1b[2J")?;
+            return self.render_full(s, legal_moves);
+        };
+
+        // the board, its edges and the columns letters take the first 18
+        // lines, the cursor is put on the line below.
+        write!(s, "\x1b[19;1H")?;
+        self.board.render_changed_squares(
+            s,
+            (&previous, previous_legal_moves),
+            legal_moves,
+            self.settings.legal_move_marker,
+        )?;
+
+        // the names and the scores are on the edge above the last row
+        write!(s, "\x1b[15;1H")?;
+        Board::render_edge(s)?;
+        self.render_scores(s)?;
+        write!(s, "\x1b[K\x1b[19;1H")?;
+        self.render_clocks(s)?;
+        // clear the text written below the board since the last render
+        write!(s, "\x1b[J")?;
+        s.flush()?;
+
+        Ok(())
+    }
+
+    /// Renders the whole board with the scores and the clocks.
+    fn render_full(&self, s: &mut StandardStream, legal_moves: Option<u64>) -> Result<()> {
+        for row in 0..8 {
+            Board::render_edge(s)?;
+
+            // print the scores
+            if row == 7 {
+                self.render_scores(s)?;
+            }
+
+            writeln!(s)?;
+
+            self.board
+                .render_row(s, row, legal_moves, self.settings.legal_move_marker)?;
+
+            // print the score
+            if row == 6 {
+                s.set_color(&style::WHITE_BOLD)?;
+                write!(s, "  SCORES:")?;
+                s.reset()?;
+            }
+
+            writeln!(s)?;
+        }
+        Board::render_edge(s)?;
+        writeln!(s)?;
+        Board::render_columns(s)?;
+        self.render_clocks(s)?;
+
+        Ok(())
+    }
+
+    /// Renders the names of the players with their scores, without the
+    /// newline.
+    fn render_scores(&self, s: &mut StandardStream) -> Result<()> {
+        let (white_score, black_score, _) = self.board.scores();
+        write!(s, "    ")?;
+
+        // the names are measured in columns of the terminal, so the wide
+        // characters don't make the line wrap.
+        s.set_color(&style::BLACK_PLAYER)?;
+        write!(
+            s,
+            "{}",
+            style::truncate_to_width(&self.black_name(), MAX_NAME_WIDTH)
+        )?;
+        s.reset()?;
+        write!(s, ": {black_score}  ")?;
+
+        s.set_color(&style::WHITE_PLAYER)?;
+        write!(
+            s,
+            "{}",
+            style::truncate_to_width(&self.white_name(), MAX_NAME_WIDTH)
+        )?;
+        s.reset()?;
+        write!(s, ": {white_score}")?;
+
+        Ok(())
+    }
+
+    /// Renders the clocks on their own line, if the game has a time control.
+    fn render_clocks(&self, s: &mut StandardStream) -> Result<()> {
+        if let Some((black, white)) = self.clocks {
+            s.set_color(&style::WHITE_BOLD)?;
+            write!(s, "  CLOCKS:")?;
+            s.reset()?;
+            writeln!(
+                s,
+                " {}: {}  {}: {}",
+                Disc::Black,
+                format_clock(black),
+                Disc::White,
+                format_clock(white)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the legal moves of the current player without storing them,
+    /// nor checking if the game is over.
+    #[inline]
+    #[must_use]
+    pub fn compute_legal_moves(&self) -> u64 {
+        self.board.legal_moves(self.turn())
+    }
+
+    /// Return the legal moves of the current player, the list is built once
+    /// per turn when the legal moves are computed. It is empty if they aren't.
+    #[inline]
+    #[must_use]
+    pub fn legal_move_list(&self) -> &[Move] {
+        &self.legal_move_list
+    }
+
+    /// Return the legal moves of the current player in the algebric order,
+    /// row by row from `a1` to `h8`, e.g: to list them. It is empty if the
+    /// legal moves aren't computed.
+    #[must_use]
+    pub fn legal_moves_sorted(&self) -> Vec<Move> {
+        let mut moves = self.legal_move_list.clone();
+        // the list is already in the order of the squares, don't rely on it
+        moves.sort_by_key(|mov| (mov.row, mov.col));
+        moves
+    }
+
+    /// Compute and store the legal moves of the current player.
+    fn legal_moves(&mut self) {
+        let outcome = match self.forfeit_legal_moves.take() {
+            // the turn of the opponent was forfeited because this player can
+            // move
+            Some(legal_moves) => TurnOutcome::Continue { legal_moves },
+            None => self.board.terminal_or_pass(self.turn()),
+        };
+        let moves = match outcome {
+            TurnOutcome::Continue { legal_moves } => legal_moves,
+            TurnOutcome::Pass { .. } | TurnOutcome::GameOver(_) => 0,
+        };
+        debug_assert_eq!(moves, self.compute_legal_moves());
+        self.current_legal_moves = Some(moves);
+        self.legal_move_list = bits(self.moves()).map(Move::from_idx).collect();
+
+        match outcome {
+            TurnOutcome::Continue { .. } => {}
+            TurnOutcome::Pass { opponent_moves } => {
+                // the opponent can play, so we forfeit this turn, his legal
+                // moves are reused on his turn
+                self.forfeit_legal_moves = Some(opponent_moves);
+                self.state = State::TurnForfeited;
+            }
+            TurnOutcome::GameOver(_) => {
+                // No one can move this is either a draw or a win.
+                // the end state is only decided here, the empty squares are
+                // counted once by `Board::end_state` whoever was stuck first.
+                let state = self
+                    .board
+                    .end_state(self.settings.scoring_rule, |winner_color| {
+                        match winner_color {
+                            Disc::White => self.white_name(),
+                            Disc::Black => self.black_name(),
+                            Disc::Empty => unreachable!(),
+                        }
+                        .into()
+                    });
+                self.state = state;
+            }
+        }
+    }
+
+    #[inline]
+    pub fn moves(&self) -> u64 {
+        self.current_legal_moves.unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use rand::{rngs::StdRng, seq::IteratorRandom, RngCore, SeedableRng};
+    use termcolor::ColorChoice;
+
+    use super::*;
+    use player::RandomPlayer;
+
+    /// Create a game between two random bots on `board`, it isn't rendered
+    /// nor recorded.
+    fn test_game(board: Board, settings: GameSettings) -> Game {
+        Game::with_board(
+            board,
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                game_record: false,
+                saves_game_dir: None,
+                ..settings
+            },
+        )
+        .unwrap()
+    }
+
+    /// Play `games` random games seeded with `seed` and return every position
+    /// reached with the player to move, the positions where a player passes
+    /// are included.
+    fn random_positions(seed: u64, games: usize) -> Vec<(Board, Disc)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut positions = Vec::new();
+
+        for _ in 0..games {
+            let mut board = Board::new();
+            let mut turn = Disc::Black;
+            while !board.is_terminal() {
+                positions.push((board.clone(), turn));
+                if let Some(idx) = bits(board.legal_moves(turn)).choose(&mut rng) {
+                    board.apply_move(turn, Move::from_idx(idx));
+                }
+                turn = !turn;
+            }
+        }
+
+        positions
+    }
+
+    #[test]
+    fn incremental_render_redraws_the_changed_squares() {
+        let positions = random_positions(887, 20);
+        for pair in positions.windows(2) {
+            let [(previous, previous_turn), (board, turn)] = pair else {
+                unreachable!()
+            };
+            let previous_legal_moves = Some(previous.legal_moves(*previous_turn));
+            let legal_moves = Some(board.legal_moves(*turn));
+            let changed = board.changed_squares(legal_moves, (previous, previous_legal_moves));
+
+            let mut s = termcolor::Ansi::new(Vec::new());
+            board
+                .render_incremental(
+                    &mut s,
+                    Some((previous, previous_legal_moves)),
+                    legal_moves,
+                    RenderStyle::default(),
+                )
+                .unwrap();
+            let out = String::from_utf8(s.into_inner()).unwrap();
+
+            // every redrawn square saves the cursor and puts it back
+            assert_eq!(out.matches("\x1b[s").count(), changed.count_ones() as usize);
+            assert_eq!(out.matches("\x1b[u").count(), changed.count_ones() as usize);
+        }
+    }
+
+    #[test]
+    fn legal_moves_always_outflank() {
+        for (board, player) in random_positions(863, 200) {
+            let legal_moves = board.legal_moves(player);
+            for idx in bits(board.empties()) {
+                let mov = Move::from_idx(idx);
+                let outflanks = board.move_outflanks(player, mov);
+
+                if legal_moves & (1 << idx) != 0 {
+                    assert_ne!(outflanks, 0, "the legal move {mov:?} outflanks nothing");
+                } else {
+                    assert_eq!(outflanks, 0, "the illegal move {mov:?} outflanks discs");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bitboard_flips_match_the_scan() {
+        for (board, player) in random_positions(870, 200) {
+            for idx in bits(board.empties()) {
+                let mov = Move::from_idx(idx);
+                assert_eq!(
+                    board.move_outflanks(player, mov),
+                    board.move_outflanks_scan(player, mov),
+                    "{player} at {} on {}",
+                    mov.to_algebric(),
+                    board.to_notation()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn is_legal_on_the_starting_board() {
+        let board = Board::new();
+        assert!(board.is_legal(Disc::Black, Move::from_algebric("d3").unwrap()));
+        assert!(!board.is_legal(Disc::Black, Move::from_algebric("a1").unwrap()));
+    }
+
+    #[test]
+    fn own_disc_next_to_the_move_outflanks_nothing() {
+        // `-XOX` on the first row: playing a1 for Black doesn't outflank the
+        // White disc, the Black disc next to a1 stops the line.
+        let board = Board::from_str(&format!("-XOX{}", "-".repeat(60))).unwrap();
+        let mov = Move::from_algebric("a1").unwrap();
+        assert_eq!(board.move_outflanks(Disc::Black, mov), 0);
+        assert!(!board.is_legal(Disc::Black, mov));
+
+        // but it does when the White disc is next to the move
+        let board = Board::from_str(&format!("-OX{}", "-".repeat(61))).unwrap();
+        assert_eq!(board.move_outflanks(Disc::Black, mov), 1 << 1);
+    }
+
+    #[test]
+    fn both_players_stuck_with_empty_squares() {
+        // Black has three discs on the first row and White one in the corner
+        // h8, no disc can be outflanked but 60 squares are still empty.
+        let board = Board::from_str(&format!("XXX{}O", "-".repeat(60))).unwrap();
+        assert!(board.is_terminal());
+
+        for (rule, winner_score, empty_squares) in [
+            (ScoringRule::ChampionshipWinnerTakesEmpties, 63, 60),
+            (ScoringRule::DiscCountOnly, 3, 0),
+        ] {
+            for first_player in [Disc::Black, Disc::White] {
+                let mut game = test_game(
+                    board.clone(),
+                    GameSettings {
+                        first_player,
+                        scoring_rule: rule,
+                        ..Default::default()
+                    },
+                );
+                game.play().unwrap();
+
+                // the empty squares are counted once, whoever is stuck first
+                assert_eq!(
+                    game.state(),
+                    &State::Winned {
+                        winner_name: String::from("Random Bot"),
+                        winner_color: Disc::Black,
+                        winner_score,
+                        loser_score: 1,
+                        empty_squares,
+                    }
+                );
+                assert_eq!(game.passes(), &[] as &[usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn both_players_stuck_with_a_draw() {
+        let board = Board::from_str(&format!("XX{}OO", "-".repeat(60))).unwrap();
+        let mut game = test_game(board, GameSettings::default());
+        game.play().unwrap();
+        assert_eq!(game.state(), &State::Draw);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn legal_move_keeps_the_disc_count_invariant() {
+        let mut board = Board::new();
+        let before = board.scores();
+        board.apply_move(Disc::Black, Move::from_algebric("d3").unwrap());
+        check_turn_invariant(before, board.scores(), Disc::Black);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic = "a turn must place exactly one disc on the board"]
+    fn bad_flip_breaks_the_disc_count_invariant() {
+        let mut board = Board::new();
+        let before = board.scores();
+        // a broken flip also putting a Black disc on the empty square d6
+        board.apply_move(Disc::Black, Move::from_algebric("d3").unwrap());
+        board.change_disc(Move::from_algebric("d6").unwrap(), Disc::Black);
+        check_turn_invariant(before, board.scores(), Disc::Black);
+    }
+
+    /// A writer accepting at most 3 bytes per write.
+    struct ShortWriter(Vec<u8>);
+
+    impl Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn save_is_fully_written_with_short_writes() {
+        let save = GameSave::from_transcript(String::from("Short writes"), "f5d6c3d3c4").unwrap();
+
+        for format in [SaveFormat::Json, SaveFormat::Ron] {
+            let mut writer = ShortWriter(Vec::new());
+            save.write_as(&mut writer, format).unwrap();
+            assert_eq!(GameSave::read_as(&writer.0[..], format).unwrap(), save);
+        }
+    }
+
+    #[test]
+    fn scoring_rule_reads_the_old_names() {
+        assert_eq!(
+            serde_json::from_str::<ScoringRule>(r#""Championship""#).unwrap(),
+            ScoringRule::ChampionshipWinnerTakesEmpties
+        );
+        assert_eq!(
+            ron::from_str::<ScoringRule>("DiscCount").unwrap(),
+            ScoringRule::DiscCountOnly
+        );
+    }
+
+    #[test]
+    fn autosave_is_throttled_by_moves() {
+        let dir = env::temp_dir().join(format!("othe-autosave-{}", std::process::id()));
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                autosave_throttle: AutosaveThrottle::Moves(5),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // play `moves` moves and return the number of autosaves
+        let play = |game: &mut Game, moves: usize| {
+            let mut writes = 0;
+            for _ in 0..moves {
+                game.legal_moves();
+                game.make_turn(game.legal_move_list()[0]).unwrap();
+
+                let before = game.last_autosave.0;
+                game.autosave();
+                if game.last_autosave.0 != before {
+                    writes += 1;
+                }
+            }
+            writes
+        };
+
+        assert_eq!(play(&mut game, 12), 2);
+        // after taking back moves, the autosaves restart from the moves kept
+        game.undo_to(3).unwrap();
+        assert_eq!(play(&mut game, 5), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn white_moves_first() {
+        let mut game = test_game(
+            Board::new(),
+            GameSettings {
+                first_player: Disc::White,
+                ..Default::default()
+            },
+        );
+        game.legal_moves();
+        assert_eq!(game.turn(), Disc::White);
+        let white_moves: Vec<_> = ["e3", "f4", "c5", "d6"]
+            .map(|mov| Move::from_algebric(mov).unwrap())
+            .into();
+        assert_eq!(game.legal_moves_sorted(), white_moves);
+
+        game.apply_algebraic("e3").unwrap();
+        assert_eq!(game.turn(), Disc::Black);
+        assert_eq!(game.board().get_disc((4, 3)), Disc::White);
+    }
+
+    #[test]
+    fn empty_first_player_is_an_error() {
+        let res = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                first_player: Disc::Empty,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(res, Err(OthelloError::InvalidPlayerColor(_))));
+    }
+
+    /// A bot playing the first legal move that sets the interrupt flag, like
+    /// Ctrl-C, once `after` moves were played.
+    #[derive(Debug)]
+    struct InterruptingPlayer {
+        color: Disc,
+        flag: InterruptFlag,
+        after: usize,
+    }
+
+    impl Player for InterruptingPlayer {
+        fn color(&self) -> Disc {
+            self.color
+        }
+
+        fn think(&self, game: &Game, _: Option<OthelloError>) -> Result<ThinkOutcome> {
+            if game.history().len() >= self.after {
+                self.flag.interrupt();
+            }
+            Ok(ThinkOutcome::Move(game.legal_move_list()[0]))
+        }
+
+        fn name(&self) -> Option<Cow<'static, str>> {
+            None
+        }
+
+        fn init_color(&mut self, color: Disc) {
+            self.color = color;
+        }
+
+        fn player_type(&self) -> PlayerType {
+            PlayerType::Bot
+        }
+    }
+
+    #[test]
+    fn interrupt_aborts_the_game_and_saves_it() {
+        let dir = env::temp_dir().join(format!("othe-interrupt-{}", std::process::id()));
+        let flag = InterruptFlag::new();
+        let player = |after| {
+            Box::new(InterruptingPlayer {
+                color: Disc::Empty,
+                flag: flag.clone(),
+                after,
+            })
+        };
+        let mut game = Game::new(
+            player(usize::MAX),
+            player(4),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                interrupt: Some(flag.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        game.play().unwrap();
+        // the move Black was thinking about during the interrupt isn't played
+        assert_eq!(game.state(), &State::Aborted);
+        assert_eq!(game.history().len(), 4);
+        let history = game.history().to_vec();
+
+        let path = game.post_play().unwrap().unwrap();
+        let save = GameSave::read_as(File::open(path).unwrap(), SaveFormat::Json).unwrap();
+        assert_eq!(save.end_state, State::Aborted);
+        assert_eq!(save.moves, history);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn handicap_game_save_round_trip() {
+        let dir = env::temp_dir().join(format!("othe-handicap-{}", std::process::id()));
+        let start = Board::with_handicap(StartKind::Parallel, Disc::Black, 2, Disc::Black).unwrap();
+        let mut game = Game::with_board(
+            start.clone(),
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.play().unwrap();
+        let (state, board) = (game.state().clone(), game.board().clone());
+
+        let path = game.post_play().unwrap().unwrap();
+        let save = GameSave::read_as(File::open(path).unwrap(), SaveFormat::Json).unwrap();
+        assert_eq!(save.start_board(), start);
+        assert_eq!(save.end_state, state);
+        save.verify().unwrap();
+        assert_eq!(save.positions().last().unwrap().board, board);
+        assert_eq!(save.to_frames().unwrap().len(), save.moves.len() + 1);
+
+        let mut ron = Vec::new();
+        save.write_as(&mut ron, SaveFormat::Ron).unwrap();
+        assert_eq!(GameSave::read_as(&ron[..], SaveFormat::Ron).unwrap(), save);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn forced_pass_save_round_trip() {
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(env::temp_dir()),
+                allow_forced_pass: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.apply_algebraic("f5").unwrap();
+        game.force_pass().unwrap();
+        // Black flips the only white disc
+        game.apply_algebraic("c3").unwrap();
+        game.legal_moves();
+        assert!(matches!(
+            game.state(),
+            State::Winned {
+                winner_color: Disc::Black,
+                ..
+            }
+        ));
+
+        let mut save = game.save.clone().unwrap();
+        save.end_state = game.state().clone();
+        assert_eq!(save.moves_with_passes().len(), 3);
+
+        let mut json = Vec::new();
+        save.write_as(&mut json, SaveFormat::Json).unwrap();
+        let mut save = GameSave::read_as(&json[..], SaveFormat::Json).unwrap();
+        save.verify().unwrap();
+        let last = save.positions().last().unwrap();
+        assert_eq!(last.player, Disc::Black);
+        assert_eq!(last.board, *game.board());
+        save.replay_with(StandardStream::stdout(ColorChoice::Never), false)
+            .unwrap();
+    }
+
+    /// Return the moves of a game where each player plays his first legal
+    /// move, until the end.
+    fn first_legal_moves_game() -> Vec<Move> {
+        let mut board = Board::new();
+        let mut turn = Disc::Black;
+        let mut moves = Vec::new();
+        loop {
+            match board.terminal_or_pass(turn) {
+                TurnOutcome::Continue { legal_moves } => {
+                    let mov = Move::from_idx(legal_moves.trailing_zeros() as u8);
+                    board.apply_move(turn, mov);
+                    moves.push(mov);
+                }
+                TurnOutcome::Pass { .. } => {}
+                TurnOutcome::GameOver(_) => return moves,
+            }
+            turn = !turn;
+        }
+    }
+
+    #[test]
+    fn replay_rejects_illegal_moves() {
+        let mut save = GameSave::from_transcript(String::from("Corrupted"), "f5d6").unwrap();
+        let a1 = Move::from_algebric("a1").unwrap();
+        save.moves.push(a1);
+
+        let res = save.replay_with(StandardStream::stdout(ColorChoice::Never), false);
+        assert!(matches!(
+            res,
+            Err(OthelloError::IllegalMoveInSequence { index: 2, mov }) if mov == a1
+        ));
+    }
+
+    #[test]
+    fn replay_rejects_a_wrong_end_state() {
+        let mut save = GameSave::from_moves(
+            String::from("Full game"),
+            first_legal_moves_game(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(save.end_state, State::Winned { .. }));
+        save.replay_with(StandardStream::stdout(ColorChoice::Never), false)
+            .unwrap();
+
+        save.end_state = State::Draw;
+        let res = save.replay_with(StandardStream::stdout(ColorChoice::Never), false);
+        assert!(matches!(
+            res,
+            Err(OthelloError::ReplayMismatch {
+                expected: State::Draw,
+                got: State::Winned { .. },
+            })
+        ));
+    }
+
+    #[test]
+    fn verify_agrees_with_the_replay() {
+        let replay = |save: &mut GameSave| {
+            save.replay_with(StandardStream::stdout(ColorChoice::Never), false)
+        };
+
+        let mut save = GameSave::from_moves(
+            String::from("Consistent"),
+            first_legal_moves_game(),
+            None,
+            None,
+        )
+        .unwrap();
+        save.verify().unwrap();
+        replay(&mut save).unwrap();
+
+        // the recorded winner was tampered with
+        let mut tampered = save.clone();
+        if let State::Winned { winner_color, .. } = &mut tampered.end_state {
+            *winner_color = !*winner_color;
+        }
+        assert!(matches!(
+            tampered.verify(),
+            Err(OthelloError::ReplayMismatch { .. })
+        ));
+        assert!(matches!(
+            replay(&mut tampered),
+            Err(OthelloError::ReplayMismatch { .. })
+        ));
+
+        // a move was replaced by an illegal one
+        let mut tampered = save.clone();
+        tampered.moves[10] = tampered.moves[0];
+        assert!(matches!(
+            tampered.verify(),
+            Err(OthelloError::IllegalMoveInSequence { index: 10, .. })
+        ));
+        assert!(matches!(
+            replay(&mut tampered),
+            Err(OthelloError::IllegalMoveInSequence { index: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn old_saves_count_the_empty_squares() {
+        // Black flips all the white discs with 51 empty squares left
+        let mut save =
+            GameSave::from_transcript(String::from("Wipeout"), "d3c3b3d2e1d6d7e3f4").unwrap();
+        let State::Winned { empty_squares, .. } = &mut save.end_state else {
+            panic!("the game is over");
+        };
+        assert_eq!(*empty_squares, 51);
+
+        // the saves made before the version 1 don't store the empty squares
+        *empty_squares = 0;
+        save.version = 0;
+        let mut json = Vec::new();
+        save.write_as(&mut json, SaveFormat::Json).unwrap();
+
+        let save = GameSave::read_as(&json[..], SaveFormat::Json).unwrap();
+        assert_eq!(save.version, GameSave::VERSION);
+        assert!(matches!(
+            save.end_state,
+            State::Winned {
+                empty_squares: 51,
+                ..
+            }
+        ));
+        save.verify().unwrap();
+    }
+
+    #[test]
+    fn timed_out_games_replay_as_timeouts() {
+        let replay = |save: &mut GameSave| {
+            save.replay_with(StandardStream::stdout(ColorChoice::Never), false)
+        };
+
+        // Black ran out of time before his second move
+        let mut save = GameSave::from_transcript(String::from("Timeout"), "f5d6").unwrap();
+        save.end_state = State::TimedOut { loser: Disc::Black };
+        save.verify().unwrap();
+        replay(&mut save).unwrap();
+
+        // it's not the turn of White
+        save.end_state = State::TimedOut { loser: Disc::White };
+        assert!(matches!(
+            save.verify(),
+            Err(OthelloError::ReplayMismatch {
+                got: State::Aborted,
+                ..
+            })
+        ));
+        assert!(matches!(
+            replay(&mut save),
+            Err(OthelloError::ReplayMismatch {
+                got: State::Aborted,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn default_save_file_name_is_sortable() {
+        let time = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().to_utc();
+        let earlier = GameSave::file_stem_at(time("2024-08-17T09:30:05.000000001Z"));
+        let later = GameSave::file_stem_at(time("2024-08-17T14:30:05Z"));
+        assert!(!earlier.contains(':'));
+        assert!(earlier < later);
+
+        let game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                saves_game_dir: Some(env::temp_dir()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let name = game.save.as_ref().unwrap().file_name(SaveFormat::Json);
+        assert!(!name.contains(':'));
+        assert!(name.ends_with(".json"));
+    }
+
+    #[test]
+    fn custom_title_names_the_save() {
+        let mut game = Game::with_board(
+            Board::new(),
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                saves_game_dir: Some(env::temp_dir()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.set_title("Club final: round 2/3");
+
+        let save = game.save.as_ref().unwrap();
+        assert_eq!(save.title, "Club final: round 2/3");
+        assert_eq!(
+            save.file_name(SaveFormat::Json),
+            "Club final_ round 2_3.json"
+        );
+    }
+
+    #[test]
+    fn clocks_count_the_thinking_time() {
+        let secs = Duration::from_secs;
+        let mut game = test_game(
+            Board::new(),
+            GameSettings {
+                time_control: Some(TimeControl {
+                    base: secs(10),
+                    increment: secs(2),
+                }),
+                ..Default::default()
+            },
+        );
+        assert_eq!(game.clocks, Some((secs(10), secs(10))));
+
+        // the elapsed times are given like a fake clock would measure them
+        assert!(game.spend_time(Disc::Black, secs(3)));
+        game.add_increment(Disc::Black);
+        assert_eq!(game.clocks, Some((secs(9), secs(10))));
+
+        assert!(!game.spend_time(Disc::White, secs(10)));
+        assert_eq!(game.clocks, Some((secs(9), Duration::ZERO)));
+        assert_eq!(game.state(), &State::TimedOut { loser: Disc::White });
+    }
+
+    /// A player counting the games it was reset for, it plays the first legal
+    /// move.
+    #[derive(Debug)]
+    struct CountingPlayer {
+        color: Disc,
+        resets: Rc<Cell<usize>>,
+    }
+
+    impl Player for CountingPlayer {
+        fn color(&self) -> Disc {
+            self.color
+        }
+
+        fn think(&self, game: &Game, _: Option<OthelloError>) -> Result<ThinkOutcome> {
+            Ok(ThinkOutcome::Move(game.legal_move_list()[0]))
+        }
+
+        fn name(&self) -> Option<Cow<'static, str>> {
+            None
+        }
+
+        fn init_color(&mut self, color: Disc) {
+            assert_eq!(self.color, Disc::Empty);
+            self.color = color;
+        }
+
+        fn reset(&mut self) {
+            self.color = Disc::Empty;
+            self.resets.set(self.resets.get() + 1);
+        }
+
+        fn player_type(&self) -> PlayerType {
+            PlayerType::Bot
+        }
+    }
+
+    #[test]
+    fn players_are_reused_with_swapped_colors() {
+        let play = |white: Box<dyn Player>, black: Box<dyn Player>| {
+            let mut game = Game::new(
+                white,
+                black,
+                StandardStream::stdout(ColorChoice::Never),
+                GameSettings {
+                    render: false,
+                    saves_game_dir: None,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            game.play().unwrap();
+            game.into_players()
+        };
+
+        let resets = Rc::new(Cell::new(0));
+        let counting = Box::new(CountingPlayer {
+            color: Disc::Empty,
+            resets: resets.clone(),
+        });
+        let (white, black) = play(counting, Box::new(RandomPlayer::new(None)));
+        assert_eq!(white.color(), Disc::White);
+
+        // the players swap their colors in the second game
+        let (white, black) = play(black, white);
+        assert_eq!(white.color(), Disc::White);
+        assert_eq!(black.color(), Disc::Black);
+        assert_eq!(resets.get(), 2);
+    }
+
+    #[test]
+    fn illegal_moves_are_not_recorded() {
+        let mut save = GameSave::from_transcript(String::from("Pushes"), "f5").unwrap();
+        let d6 = Move::from_algebric("d6").unwrap();
+        assert!(matches!(
+            save.try_push_move(Move::from_algebric("a1").unwrap()),
+            Err(OthelloError::IllegalMove { row: 0, col: 0 })
+        ));
+        save.try_push_move(d6).unwrap();
+        assert_eq!(save.moves.len(), 2);
+
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                saves_game_dir: Some(env::temp_dir()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.apply_algebraic("f5").unwrap();
+        assert!(game.apply_algebraic("a1").is_err());
+        game.apply_algebraic("d6").unwrap();
+        assert_eq!(game.save.as_ref().unwrap().moves, save.moves);
+    }
+
+    #[test]
+    fn handicap_leaves_a_move_to_the_first_player() {
+        // the four corners and the center are black, White has no disc
+        let board = Board::with_handicap(StartKind::Parallel, Disc::Black, 4, Disc::Black);
+        assert!(board.is_ok());
+
+        for first_player in [Disc::Black, Disc::White] {
+            let board =
+                Board::with_handicap(StartKind::Diagonal, Disc::White, 4, first_player).unwrap();
+            let (white, black, _) = board.scores();
+            assert_eq!((white, black), (6, 2));
+            assert_ne!(board.legal_moves(first_player), 0);
+        }
+
+        assert!(matches!(
+            Board::with_handicap(StartKind::Diagonal, Disc::Black, 2, Disc::Empty),
+            Err(OthelloError::InvalidHandicap)
+        ));
+    }
+
+    #[test]
+    fn replay_summary_names_the_opening_of_standard_starts() {
+        let summary = |save: &GameSave| {
+            let mut s = NoColor::new(Vec::new());
+            save.render_summary(&mut s, &save.start_board()).unwrap();
+            String::from_utf8(s.into_inner()).unwrap()
+        };
+
+        let save = GameSave::from_transcript("wipeout".to_owned(), "d3c3b3d2e1d6d7e3f4").unwrap();
+        assert!(summary(&save).contains("  Opening: Diagonal\n"));
+        assert!(summary(&save).contains("  Moves played: 9\n"));
+
+        let mut white_first = save.clone();
+        white_first.first_player = Disc::White;
+        assert!(!summary(&white_first).contains("Opening"));
+        let mut parallel = save;
+        parallel.start = Some(Board::with_start(StartKind::Parallel));
+        assert!(!summary(&parallel).contains("Opening"));
+    }
+
+    #[test]
+    fn read_input_stops_at_the_end_of_the_input() {
+        assert_eq!(read_input(&mut io::Cursor::new("")).unwrap(), None);
+
+        let mut input = io::Cursor::new("e6\r\n\nd3");
+        assert_eq!(read_input(&mut input).unwrap().as_deref(), Some("e6"));
+        assert_eq!(read_input(&mut input).unwrap().as_deref(), Some(""));
+        // the last line has no newline
+        assert_eq!(read_input(&mut input).unwrap().as_deref(), Some("d3"));
+        assert_eq!(read_input(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn prompt_is_flushed() {
+        // the prompt is in the vector only once the buffer is flushed
+        let mut s = io::BufWriter::new(Vec::new());
+        prompt(&mut s, format_args!("{} player's type (1): ", Disc::Black)).unwrap();
+        assert_eq!(s.get_ref(), b"Black player's type (1): ");
+        assert!(s.buffer().is_empty());
+    }
+
+    #[test]
+    fn empties_and_occupied_cover_the_board() {
+        let board = Board::new();
+        assert_eq!(board.empties().count_ones(), 60);
+        assert_eq!(board.empty_count(), 60);
+        assert_eq!(board.empties() | board.occupied(), u64::MAX);
+        assert_eq!(board.occupied(), board.black_bb() | board.white_bb());
+
+        for (board, _) in random_positions(906, 5) {
+            assert_eq!(board.empties() | board.occupied(), u64::MAX);
+            assert_eq!(board.empties() & board.occupied(), 0);
+            assert_eq!(board.empties().count_ones(), board.empty_count() as u32);
+        }
+    }
+
+    #[test]
+    fn transcripts_replay_to_the_end_of_the_game() {
+        let moves = first_legal_moves_game();
+        let transcript: String = moves.iter().map(|mov| mov.to_algebric() + " ").collect();
+        let save = GameSave::from_transcript("Transcript".to_owned(), &transcript).unwrap();
+        assert_eq!(save.moves, moves);
+        assert_eq!(save.black_name.as_deref(), Some("Unknown"));
+
+        let (entries, state) = save.replay_silently().unwrap();
+        assert_eq!(entries.len(), moves.len() + save.passes.len());
+        assert!(matches!(state, State::Winned { .. } | State::Draw));
+        assert_eq!(state, save.end_state);
+        save.verify().unwrap();
+
+        let mut board = Board::new();
+        board.play_with_passes(Disc::Black, &entries).unwrap();
+        assert!(board.is_terminal());
+    }
+
+    #[test]
+    fn saves_diverge_at_the_first_different_move() {
+        let save = |transcript| GameSave::from_transcript("diff".to_owned(), transcript).unwrap();
+        let mov = |algebric| Some(Move::from_algebric(algebric).unwrap());
+
+        // the Tiger and the Cow share f5 d6
+        let tiger = save("f5d6c3d3c4");
+        let cow = save("f5d6c5");
+        assert_eq!(
+            tiger.diff(&cow),
+            Some(MovesDiff {
+                ply: 2,
+                left: mov("c3"),
+                right: mov("c5"),
+            })
+        );
+
+        // one game is the beginning of the other
+        let perpendicular = save("f5d6");
+        assert_eq!(
+            perpendicular.diff(&tiger),
+            Some(MovesDiff {
+                ply: 2,
+                left: None,
+                right: mov("c3"),
+            })
+        );
+        assert_eq!(tiger.diff(&perpendicular).unwrap().right, None);
+        assert_eq!(tiger.diff(&tiger.clone()), None);
+    }
+
+    #[test]
+    fn board_diff_lists_the_placed_and_flipped_discs() {
+        let start = Board::new();
+        let mut board = start.clone();
+        board.apply_move(Disc::Black, Move::from_algebric("f5").unwrap());
+
+        let e5 = Move::from_algebric("e5").unwrap();
+        let f5 = Move::from_algebric("f5").unwrap();
+        assert_eq!(
+            start.diff(&board),
+            [
+                (e5, Disc::White, Disc::Black),
+                (f5, Disc::Empty, Disc::Black)
+            ]
+        );
+        assert_eq!(
+            board.diff(&start),
+            [
+                (e5, Disc::Black, Disc::White),
+                (f5, Disc::Black, Disc::Empty)
+            ]
+        );
+        assert!(board.diff(&board).is_empty());
+    }
+
+    #[test]
+    fn frontier_discs_and_corners() {
+        // the four discs of the start are all on the frontier
+        let board = Board::new();
+        for player in [Disc::Black, Disc::White] {
+            assert_eq!(board.frontier_discs(player), 2);
+            assert_eq!(board.count_corners(player), 0);
+        }
+
+        // the corner a1 is surrounded by White discs, only they touch an
+        // empty square
+        let board = Board::from_str(&format!("XO------OO{}", "-".repeat(54))).unwrap();
+        assert_eq!(board.frontier_discs(Disc::Black), 0);
+        assert_eq!(board.count_corners(Disc::Black), 1);
+        assert_eq!(board.frontier_discs(Disc::White), 3);
+        assert_eq!(board.count_corners(Disc::White), 0);
+    }
+
+    #[test]
+    fn starting_board_renders_to_a_string() {
+        let board = Board::new();
+        let expected = "\
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 1
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 2
++---+---+---+---+---+---+---+---+
+|   |   |   | • |   |   |   |   | 3
++---+---+---+---+---+---+---+---+
+|   |   | • | W | B |   |   |   | 4
++---+---+---+---+---+---+---+---+
+|   |   |   | B | W | • |   |   | 5
++---+---+---+---+---+---+---+---+
+|   |   |   |   | • |   |   |   | 6
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 7
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 8
++---+---+---+---+---+---+---+---+
+  a   b   c   d   e   f   g   h
+";
+        assert_eq!(
+            board.render_to_string(Some(board.legal_moves(Disc::Black)), RenderStyle::Unicode),
+            expected
+        );
+        assert_eq!(
+            board.render_to_string(Some(board.legal_moves(Disc::Black)), RenderStyle::Ascii),
+            expected.replace('•', ".")
+        );
+        assert_eq!(
+            board.render_to_string(None, RenderStyle::Unicode),
+            expected.replace('•', " ")
+        );
+    }
+
+    #[test]
+    fn legal_moves_are_drawn_with_the_custom_marker() {
+        let mut settings = GameSettings::default();
+        settings.set_legal_move_marker('x').unwrap();
+        assert!(matches!(
+            settings.set_legal_move_marker('オ'),
+            Err(OthelloError::InvalidMarker('オ'))
+        ));
+        assert_eq!(settings.legal_move_marker, 'x');
+
+        let board = Board::new();
+        let mut s = NoColor::new(Vec::new());
+        for row in 2..6 {
+            board
+                .render_row(
+                    &mut s,
+                    row,
+                    Some(board.legal_moves(Disc::Black)),
+                    settings.legal_move_marker,
+                )
+                .unwrap();
+            writeln!(s).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(s.into_inner()).unwrap(),
+            "\
+|   |   |   | x |   |   |   |   | 3
+|   |   | x | W | B |   |   |   | 4
+|   |   |   | B | W | x |   |   | 5
+|   |   |   |   | x |   |   |   | 6
+"
+        );
+    }
+
+    #[test]
+    fn ascii_art_of_the_starting_board() {
+        let board = Board::new();
+        assert_eq!(
+            board.to_ascii_art(BorderStyle::Ascii),
+            "\
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 1
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 2
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 3
++---+---+---+---+---+---+---+---+
+|   |   |   | W | B |   |   |   | 4
++---+---+---+---+---+---+---+---+
+|   |   |   | B | W |   |   |   | 5
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 6
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 7
++---+---+---+---+---+---+---+---+
+|   |   |   |   |   |   |   |   | 8
++---+---+---+---+---+---+---+---+
+  a   b   c   d   e   f   g   h
+"
+        );
+        assert_eq!(
+            board.to_ascii_art(BorderStyle::Unicode),
+            "\
+┌───┬───┬───┬───┬───┬───┬───┬───┐
+│   │   │   │   │   │   │   │   │ 1
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │   │   │   │   │   │ 2
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │   │   │   │   │   │ 3
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │ W │ B │   │   │   │ 4
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │ B │ W │   │   │   │ 5
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │   │   │   │   │   │ 6
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │   │   │   │   │   │ 7
+├───┼───┼───┼───┼───┼───┼───┼───┤
+│   │   │   │   │   │   │   │   │ 8
+└───┴───┴───┴───┴───┴───┴───┴───┘
+  a   b   c   d   e   f   g   h
+"
+        );
+    }
+
+    #[test]
+    fn outcome_of_full_boards() {
+        let black_majority =
+            Board::from_str(&format!("{}{}", "X".repeat(40), "O".repeat(24))).unwrap();
+        assert!(black_majority.is_terminal());
+        assert_eq!(black_majority.outcome(), Some(Outcome::Win(Disc::Black)));
+
+        let equal = Board::from_str(&"XO".repeat(32)).unwrap();
+        assert!(equal.is_terminal());
+        assert_eq!(equal.outcome(), Some(Outcome::Draw));
+
+        // the game goes on, whoever has more discs
+        let board = Board::new();
+        assert!(!board.is_terminal());
+        assert_eq!(board.outcome(), None);
+    }
+
+    #[test]
+    fn turns_are_passed_then_the_game_is_over() {
+        // Black can't take the White disc in the corner a1, White can play c1
+        let pass = Board::from_str(&format!("OX{}", "-".repeat(62))).unwrap();
+        let c1 = 1 << Move::from_algebric("c1").unwrap().into_idx();
+        assert_eq!(
+            pass.terminal_or_pass(Disc::Black),
+            TurnOutcome::Pass { opponent_moves: c1 }
+        );
+        assert_eq!(
+            pass.terminal_or_pass(Disc::White),
+            TurnOutcome::Continue { legal_moves: c1 }
+        );
+
+        // c1 takes the last Black disc, no one can move
+        let mut board = pass;
+        board.apply_move(Disc::White, Move::from_algebric("c1").unwrap());
+        for side in [Disc::Black, Disc::White] {
+            assert_eq!(
+                board.terminal_or_pass(side),
+                TurnOutcome::GameOver(Outcome::Win(Disc::White))
+            );
+        }
+    }
+
+    #[test]
+    fn results_of_a_draw_and_of_a_win() {
+        let board = Board::from_str(&format!("XX{}OO", "-".repeat(60))).unwrap();
+        let mut game = test_game(board, GameSettings::default());
+        assert_eq!(game.result(), None);
+        game.play().unwrap();
+        assert_eq!(
+            game.result(),
+            Some(GameResult {
+                winner: None,
+                black_score: 2,
+                white_score: 2,
+                terminal: State::Draw,
+            })
+        );
+
+        // White wins, the empty squares are given to him
+        let board = Board::from_str(&format!("XX{}OOO", "-".repeat(59))).unwrap();
+        let mut game = test_game(board, GameSettings::default());
+        game.play().unwrap();
+        let result = game.result().unwrap();
+        assert_eq!(result.winner, Some(Disc::White));
+        assert_eq!((result.black_score, result.white_score), (2, 62));
+        assert!(matches!(
+            result.terminal,
+            State::Winned {
+                winner_color: Disc::White,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn bit_iterator_matches_a_scan_of_the_bits() {
+        let mut rng = StdRng::seed_from_u64(867);
+        let bitfields = [
+            0,
+            1,
+            1 << 63,
+            u64::MAX,
+            Board::CORNERS,
+            Board::new().legal_moves(Disc::Black),
+        ];
+        for bitfield in bitfields
+            .into_iter()
+            .chain((0..100).map(|_| rng.next_u64()))
+        {
+            let scanned: Vec<u8> = (0..64).filter(|idx| bitfield & 1 << idx != 0).collect();
+            assert_eq!(bits(bitfield).collect::<Vec<_>>(), scanned);
+            assert_eq!(bits(bitfield).len(), scanned.len());
+            assert_eq!(bitfield_to_indexes(bitfield), scanned);
+        }
+    }
+
+    #[test]
+    fn square_sets_have_the_right_bits() {
+        let squares = |set: u64| -> Vec<String> {
+            bits(set)
+                .map(|idx| Move::from_idx(idx).to_algebric())
+                .collect()
+        };
+        assert_eq!(squares(Board::CORNERS), ["a1", "h1", "a8", "h8"]);
+        assert_eq!(squares(Board::X_SQUARES), ["b2", "g2", "b7", "g7"]);
+        assert_eq!(
+            squares(Board::C_SQUARES),
+            ["b1", "g1", "a2", "h2", "a7", "h7", "b8", "g8"]
+        );
+
+        let on_edge = |idx: u8| [0, 7].contains(&(idx % 8)) || [0, 7].contains(&(idx / 8));
+        let edges = (0..64)
+            .filter(|&idx| on_edge(idx))
+            .fold(0, |set, idx| set | 1 << idx);
+        assert_eq!(Board::EDGES, edges);
+        assert_eq!(Board::EDGES.count_ones(), 28);
+        // the corners and the C-squares are on the edges, not the X-squares
+        assert_eq!(
+            Board::EDGES & (Board::CORNERS | Board::C_SQUARES),
+            Board::CORNERS | Board::C_SQUARES
+        );
+        assert_eq!(Board::EDGES & Board::X_SQUARES, 0);
+    }
+
+    #[test]
+    fn bitboards_round_trip() {
+        for (board, _) in random_positions(878, 3) {
+            let (black, white) = board.to_bitboards();
+            assert_eq!(Board::from_bitboards(black, white).unwrap(), board);
+        }
+
+        let (black, white) = Board::new().to_bitboards();
+        assert!(matches!(
+            // e4 is black, it is set in both bitboards
+            Board::from_bitboards(black, white | 1 << 28),
+            Err(OthelloError::OverlappingBitboards(overlap)) if overlap == 1 << 28
+        ));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        for (board, _) in random_positions(899, 3) {
+            assert_eq!(Board::from_bytes(&board.to_bytes()).unwrap(), board);
+        }
+
+        // little-endian, e4 is the bit 28 so the fourth byte of each half
+        let mut bytes = Board::new().to_bytes();
+        assert_eq!(bytes[3] & 0x10, 0x10);
+        bytes[8 + 3] |= 0x10;
+        assert!(matches!(
+            Board::from_bytes(&bytes),
+            Err(OthelloError::OverlappingBitboards(overlap)) if overlap == 1 << 28
+        ));
+    }
+
+    #[test]
+    fn legal_move_list_is_cached_for_the_turn() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        assert!(game.legal_move_list().is_empty());
+
+        game.legal_moves();
+        let first = game.legal_move_list();
+        let second = game.legal_move_list();
+        // the same slice is borrowed, it isn't built again
+        assert!(std::ptr::eq(first, second));
+        let algebric: Vec<_> = first.iter().copied().map(Move::to_algebric).collect();
+        assert_eq!(algebric, ["d3", "c4", "f5", "e6"]);
+
+        game.make_turn(first[0]).unwrap();
+        game.next_turn();
+        assert!(game.legal_move_list().is_empty());
+        game.legal_moves();
+        assert_eq!(game.legal_move_list().len(), 3);
+    }
+
+    /// Parse the moves written in algebric notation.
+    fn algebric_moves(moves: &[&str]) -> Vec<Move> {
+        moves
+            .iter()
+            .map(|mov| Move::from_algebric(mov).unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn history_lists_the_moves_in_order() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        assert!(game.history().is_empty());
+
+        game.apply_algebraic("f5").unwrap();
+        game.apply_algebraic("d6").unwrap();
+        assert_eq!(game.history(), algebric_moves(&["f5", "d6"]));
+        assert_eq!(game.move_history(), game.history());
+    }
+
+    #[test]
+    fn undo_to_the_start() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        for mov in ["f5", "d6", "c3"] {
+            game.apply_algebraic(mov).unwrap();
+        }
+        assert!(matches!(game.undo_to(4), Err(OthelloError::InvalidPly(4))));
+        assert_eq!(game.history().len(), 3);
+
+        game.undo_to(0).unwrap();
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.turn(), Disc::Black);
+        assert!(game.history().is_empty());
+        assert_eq!(game.state(), &State::Playing);
+    }
+
+    #[test]
+    fn compute_legal_moves_leaves_the_game_untouched() {
+        let game = test_game(Board::new(), GameSettings::default());
+        let moves = game.compute_legal_moves();
+        assert_eq!(
+            bits(moves).map(Move::from_idx).collect::<Vec<_>>(),
+            algebric_moves(&["d3", "c4", "f5", "e6"])
+        );
+        assert_eq!(game.current_legal_moves, None);
+        assert!(game.legal_move_list().is_empty());
+    }
+
+    #[test]
+    fn discs_placed_and_move_number() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        assert_eq!(game.board.discs_placed(), 4);
+        assert_eq!(game.move_number(), 1);
+
+        for mov in ["f5", "d6", "c3"] {
+            game.apply_algebraic(mov).unwrap();
+        }
+        assert_eq!(game.board.discs_placed(), 7);
+        assert_eq!(game.move_number(), 4);
+    }
+
+    #[test]
+    fn history_is_kept_with_and_without_recording() {
+        for game_record in [false, true] {
+            let mut game = Game::with_board(
+                Board::new(),
+                Box::new(RandomPlayer::new(None)),
+                Box::new(RandomPlayer::new(None)),
+                StandardStream::stdout(ColorChoice::Never),
+                GameSettings {
+                    render: false,
+                    game_record,
+                    // the game is recorded only with a saves directory, it
+                    // isn't written without autosave
+                    saves_game_dir: Some(env::temp_dir()),
+                    autosave: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            for mov in ["f5", "d6", "c3"] {
+                game.apply_algebraic(mov).unwrap();
+            }
+
+            assert_eq!(game.save.is_some(), game_record);
+            assert_eq!(game.history(), algebric_moves(&["f5", "d6", "c3"]));
+            if let Some(save) = &game.save {
+                assert_eq!(save.moves, game.history());
+            }
+        }
+    }
+
+    #[test]
+    fn apply_moves_returns_the_board_after_each_move() {
+        let moves = algebric_moves(&["f5", "d6", "c3"]);
+        let boards = Board::new().apply_moves(Disc::Black, &moves).unwrap();
+        assert_eq!(boards.len(), 3);
+
+        // no one passes, the players alternate
+        let mut board = Board::new();
+        for ((mov, after), turn) in
+            moves
+                .iter()
+                .zip(&boards)
+                .zip([Disc::Black, Disc::White, Disc::Black])
+        {
+            board.apply_move(turn, *mov);
+            assert_eq!(&board, after);
+        }
+
+        // d6 is illegal for Black
+        let moves = algebric_moves(&["f5", "f4", "d6"]);
+        assert!(matches!(
+            Board::new().apply_moves(Disc::Black, &moves),
+            Err(OthelloError::IllegalMoveInSequence { index: 2, mov }) if mov == moves[2]
+        ));
+    }
+
+    #[test]
+    fn play_sequence_of_the_tiger() {
+        let mut board = Board::new();
+        let turn = board
+            .play_sequence(
+                Disc::Black,
+                &algebric_moves(&["f5", "d6", "c3", "d3", "c4"]),
+            )
+            .unwrap();
+        assert_eq!(turn, Disc::White);
+        assert_eq!(
+            board.to_notation(),
+            format!(
+                "{}--XO------XXX------OXX-----O----{}",
+                "-".repeat(16),
+                "-".repeat(16)
+            )
+        );
+
+        // the moves before the illegal one stay on the board
+        let mut board = Board::new();
+        assert!(matches!(
+            board.play_sequence(Disc::Black, &algebric_moves(&["f5", "f5"])),
+            Err(OthelloError::IllegalMoveInSequence { index: 1, .. })
+        ));
+        assert_eq!(board.discs_placed(), 5);
+    }
+
+    #[test]
+    fn positions_yield_the_flipped_discs() {
+        let save = GameSave::from_transcript("Flips".to_owned(), "f5d6c3d3c4").unwrap();
+        let mut positions = save.positions();
+
+        // f5 flips e5 only
+        let first = positions.next().unwrap();
+        assert_eq!(first.player, Disc::Black);
+        assert_eq!(first.mov, Move::from_algebric("f5").unwrap());
+        assert_eq!(
+            first.flips,
+            1 << Move::from_algebric("e5").unwrap().into_idx()
+        );
+        assert_eq!(
+            first.flips,
+            Board::new().move_outflanks(Disc::Black, first.mov)
+        );
+
+        let mut board = first.board;
+        for played in positions {
+            assert_eq!(
+                played.flips,
+                board.move_outflanks(played.player, played.mov)
+            );
+            board = played.board;
+        }
+    }
+
+    #[test]
+    fn outflanks_in_two_directions() {
+        // a1 captures b1 along the row and a2 along the column, b2 is empty
+        let board = Board::from_str(&format!("-OX-----O-------X{}", "-".repeat(47))).unwrap();
+        let a1 = Move::from_algebric("a1").unwrap();
+        let b1 = 1 << Move::from_algebric("b1").unwrap().into_idx();
+        let a2 = 1 << Move::from_algebric("a2").unwrap().into_idx();
+
+        let by_direction = board.outflanks_by_direction(Disc::Black, a1);
+        let mut expected = [0; 8];
+        // the directions (1, 0) and (0, 1) of `DIRECTIONS`
+        expected[4] = b1;
+        expected[6] = a2;
+        assert_eq!(by_direction, expected);
+        assert_eq!(board.move_outflanks(Disc::Black, a1), b1 | a2);
+    }
+
+    #[test]
+    fn disc_diff_is_symmetric() {
+        let board = Board::new();
+        assert_eq!(board.disc_diff(Disc::Black), 0);
+        assert_eq!(board.disc_diff(Disc::White), 0);
+
+        for (board, _) in random_positions(894, 3) {
+            let (white, black, _) = board.scores();
+            assert_eq!(board.disc_diff(Disc::Black), black as i32 - white as i32);
+            assert_eq!(board.disc_diff(Disc::Black), -board.disc_diff(Disc::White));
+        }
+    }
+
+    #[test]
+    fn from_rows_writes_the_starting_board() {
+        use Disc::{Black as B, Empty as E, White as W};
+
+        let empty = [E; 8];
+        let board = Board::from_rows([
+            empty,
+            empty,
+            empty,
+            [E, E, E, W, B, E, E, E],
+            [E, E, E, B, W, E, E, E],
+            empty,
+            empty,
+            empty,
+        ]);
+        assert_eq!(board, Board::new());
+    }
+
+    #[test]
+    fn correct_games_never_repeat_a_position() {
+        for _ in 0..20 {
+            let mut game = test_game(
+                Board::new(),
+                GameSettings {
+                    check_repeated_positions: true,
+                    ..Default::default()
+                },
+            );
+            game.play().unwrap();
+            assert!(game.board.is_terminal());
+            assert_eq!(game.seen_positions.len(), game.history().len() + 1);
+        }
+    }
+
+    #[test]
+    fn influence_on_the_starting_board() {
+        let board = Board::new();
+        let idx = |algebric| Move::from_algebric(algebric).unwrap().into_idx();
+        let black = board.influence(Disc::Black);
+        let white = board.influence(Disc::White);
+
+        // d4 and d5 below d3, e4 on its diagonal
+        assert_eq!((black[idx("d3")], white[idx("d3")]), (2, 1));
+        // d4 and e5 on the diagonal of c3
+        assert_eq!((black[idx("c3")], white[idx("c3")]), (0, 2));
+        // no disc is next to a1 and the occupied squares have no influence
+        assert_eq!((black[idx("a1")], white[idx("a1")]), (0, 0));
+        assert_eq!((black[idx("d4")], white[idx("d4")]), (0, 0));
+    }
+
+    #[test]
+    fn starts_have_their_center_discs() {
+        let center = |board: &Board| -> Vec<Disc> {
+            ["d4", "e4", "d5", "e5"]
+                .iter()
+                .map(|algebric| {
+                    let mov = Move::from_algebric(algebric).unwrap();
+                    board.get_disc((mov.col, mov.row))
+                })
+                .collect()
+        };
+        let diagonal = Board::with_start(StartKind::Diagonal);
+        let parallel = Board::with_start(StartKind::Parallel);
+        assert_eq!(diagonal, Board::new());
+        assert_eq!(
+            center(&diagonal),
+            [Disc::White, Disc::Black, Disc::Black, Disc::White]
+        );
+        assert_eq!(
+            center(&parallel),
+            [Disc::White, Disc::Black, Disc::White, Disc::Black]
+        );
+        assert_ne!(diagonal, parallel);
+        for board in [diagonal, parallel] {
+            assert_eq!(board.scores(), (2, 2, 60));
+        }
+    }
+
+    #[test]
+    fn from_discs_builds_the_starting_board() {
+        let discs: Vec<_> = [
+            ("d4", Disc::White),
+            ("e4", Disc::Black),
+            ("d5", Disc::Black),
+            ("e5", Disc::White),
+        ]
+        .into_iter()
+        .map(|(algebric, disc)| (Move::from_algebric(algebric).unwrap(), disc))
+        .collect();
+        assert_eq!(Board::from_discs(&discs), Board::new());
+        assert_eq!(Board::from_discs(&[]).empty_count(), 64);
     }
 
-    fn turn(&self) -> Disc {
-        debug_assert_ne!(self.turn, Disc::Empty);
-        self.turn
+    #[test]
+    #[should_panic]
+    fn from_discs_rejects_squares_out_of_the_board() {
+        let _ = Board::from_discs(&[(Move { col: 8, row: 0 }, Disc::Black)]);
     }
 
-    fn is_legal(bitfield: u64, index: usize) -> bool {
-        (bitfield & (1 << index)) != 0
+    #[test]
+    fn squares_in_both_orientations() {
+        let f5 = Move { col: 5, row: 4 };
+        assert_eq!(
+            Move::from_algebric_with("f5", Orientation::Standard).unwrap(),
+            f5
+        );
+        assert_eq!(
+            Move::from_algebric_with("6e", Orientation::Transposed).unwrap(),
+            f5
+        );
+        assert_eq!(f5.to_algebric_with(Orientation::Transposed), "6e");
+        assert_eq!(f5.to_algebric(), "f5");
+
+        // each orientation rejects the other one
+        assert!(Move::from_algebric_with("6e", Orientation::Standard).is_err());
+        assert!(Move::from_algebric_with("f5", Orientation::Transposed).is_err());
+
+        for idx in 0..64 {
+            let mov = Move::from_idx(idx);
+            for orientation in [Orientation::Standard, Orientation::Transposed] {
+                let algebric = mov.to_algebric_with(orientation);
+                assert_eq!(
+                    Move::from_algebric_with(&algebric, orientation).unwrap(),
+                    mov
+                );
+            }
+        }
     }
 
-    pub fn is_legal_move(&self, index: usize) -> Result<bool> {
-        let Some(moves) = self.current_legal_moves else {
-            return Err(OthelloError::LegalMovesNotComputed);
+    #[test]
+    fn discs_serialize_as_single_characters() {
+        let discs = [Disc::Black, Disc::White, Disc::Empty];
+        assert_eq!(serde_json::to_string(&discs).unwrap(), r#"["B","W","-"]"#);
+
+        let short: Vec<Disc> = serde_json::from_str(r#"["B","W","-"]"#).unwrap();
+        assert_eq!(short, discs);
+        // the long names written before
+        let long: Vec<Disc> = serde_json::from_str(r#"["Black","White","Empty"]"#).unwrap();
+        assert_eq!(long, discs);
+        assert!(serde_json::from_str::<Disc>(r#""X""#).is_err());
+    }
+
+    #[test]
+    fn states_are_displayed_as_sentences() {
+        let mut winned = State::Winned {
+            winner_name: "Alice".to_owned(),
+            winner_color: Disc::Black,
+            winner_score: 40,
+            loser_score: 24,
+            empty_squares: 0,
         };
-        Ok(Self::is_legal(moves, index))
+        assert_eq!(winned.to_string(), "Alice (Black) wins with 40-24.");
+        if let State::Winned { empty_squares, .. } = &mut winned {
+            *empty_squares = 7;
+        }
+        assert_eq!(
+            winned.to_string(),
+            "Alice (Black) wins with 40-24 (discs 33-24, +7 empties)."
+        );
+        assert_eq!(State::Draw.to_string(), "The game ended in a draw.");
+        assert_eq!(
+            State::TimedOut { loser: Disc::White }.to_string(),
+            "White ran out of time, Black wins."
+        );
     }
 
-    fn make_turn(&mut self, mov @ Move { col, row }: Move) -> Result<()> {
-        // ensure the move is inside the legal moves.
-        let idx = (row * 8 + col) as u64;
-        if !self.is_legal_move(idx as usize)? {
-            return Err(OthelloError::IllegalMove { row, col });
+    #[test]
+    fn human_plays_the_requested_color() {
+        for human_is_black in [true, false] {
+            let game =
+                Game::human_vs_bot(Some("Alice".to_owned()), BuiltinBot::Random, human_is_black);
+            let (human, bot) = if human_is_black {
+                (&game.black_player, &game.white_player)
+            } else {
+                (&game.white_player, &game.black_player)
+            };
+            assert_eq!(human.player_type(), PlayerType::Human);
+            assert_eq!(human.force_name(), "Alice");
+            assert_eq!(bot.player_type(), PlayerType::Bot);
+            assert_eq!(
+                human.color(),
+                if human_is_black {
+                    Disc::Black
+                } else {
+                    Disc::White
+                }
+            );
+            assert_eq!(bot.color(), !human.color());
         }
-        self.board.change_disc(mov, self.turn);
-        let outflanks = self.board.move_outflanks(self.turn, mov);
-        self.board.put_discs(outflanks, self.turn);
+    }
 
-        self.next_turn();
+    #[test]
+    fn resignation_is_suggested_late_and_far_behind() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        let behind = |_: &Board, _: Disc| -500;
+        let even = |_: &Board, _: Disc| 0;
 
-        Ok(())
+        // too early, however far behind
+        assert!(!game.should_suggest_resign(&behind, 100));
+
+        let moves = first_legal_moves_game();
+        for mov in &moves[..Game::MIN_RESIGN_MOVE as usize - 1] {
+            game.apply_algebraic(&mov.to_algebric()).unwrap();
+        }
+        assert_eq!(game.move_number(), Game::MIN_RESIGN_MOVE);
+        assert!(game.should_suggest_resign(&behind, 100));
+        // the threshold itself isn't past it
+        assert!(!game.should_suggest_resign(&behind, 500));
+        assert!(!game.should_suggest_resign(&even, 100));
     }
 
-    fn next_turn(&mut self) {
-        // Change the turn to the opponent
-        self.turn = !self.turn;
-        // Reset the current legal moves to `None`, just a simple safety used
-        // not to confuse between Black's and White's legal moves
-        self.current_legal_moves = None;
-        // Set the state to playing, the player before could of had a State of
-        // `TurnForfeited` and if the other player can play it must not forfeit
-        // is turn
-        self.state = State::Playing;
+    #[test]
+    fn forfeited_turns_reuse_the_legal_moves_of_the_opponent() {
+        let mut passes = 0;
+        for (board, turn) in random_positions(897, 50) {
+            let TurnOutcome::Pass { opponent_moves } = board.terminal_or_pass(turn) else {
+                continue;
+            };
+            passes += 1;
+
+            let mut game = test_game(
+                board.clone(),
+                GameSettings {
+                    first_player: turn,
+                    ..Default::default()
+                },
+            );
+            game.legal_moves();
+            assert_eq!(game.state(), &State::TurnForfeited);
+            game.next_turn();
+            game.legal_moves();
+            assert_eq!(game.forfeit_legal_moves, None);
+            // the reused moves are the ones computed from scratch
+            assert_eq!(game.current_legal_moves, Some(opponent_moves));
+            assert_eq!(opponent_moves, board.legal_moves(!turn));
+            assert_eq!(
+                game.legal_move_list(),
+                bits(opponent_moves).map(Move::from_idx).collect::<Vec<_>>()
+            );
+        }
+        assert!(passes > 0, "no turn was forfeited in the random games");
     }
 
-    /// Start the game of Othello between the two players
-    pub fn play(&mut self) -> Result<()> {
-        loop {
-            self.legal_moves();
-            if self.current_player().render_board() {
-                self.render(None)?;
-            }
+    #[test]
+    fn quitting_to_the_menu_saves_an_aborted_game() {
+        use player::{HumanPlayer, LineReader};
 
-            match &self.state {
-                State::Playing => {}
-                State::Winned {
-                    winner_color,
-                    winner_name,
-                    winner_score,
-                    loser_score,
-                } => {
-                    let s = &mut *self.stream.borrow_mut();
+        let dir = env::temp_dir().join(format!("othe-quit-{}", std::process::id()));
+        let mut lines = vec!["f5", "menu"].into_iter();
+        let reader = LineReader::new(move |_| Ok(lines.next().map(str::to_owned)));
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(HumanPlayer::new(None).with_line_reader(reader)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                autosave: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-                    writeln!(s)?;
-                    writeln!(
-                        s,
-                        "  Congratulation, {} ({})! you win with {}-{}",
-                        winner_name, winner_color, winner_score, loser_score
-                    )?;
-                    break;
-                }
-                State::Draw => {
-                    let s = &mut *self.stream.borrow_mut();
-                    writeln!(s)?;
-                    writeln!(s, "  The game ended in a draw, congrats for both of you.")?;
-                    break;
-                }
-                State::TurnForfeited => {
-                    // the current player can't play so we pass the turn to the
-                    // opponent that can play.
-                    {
-                        let s = &mut *self.stream.borrow_mut();
-                        writeln!(
-                            s,
-                            "The turn of {} has been forfeited, he cannot play.",
-                            self.turn()
-                        )?;
-                    }
-                    self.next_turn();
-                    continue;
-                }
-            }
+        game.play().unwrap();
+        assert_eq!(game.state(), &State::Aborted);
+        assert_eq!(game.history().len(), 2);
 
-            let mut previous_err = None;
-            let mov = loop {
-                let res = self.player_think(previous_err);
+        let path = game.post_play().unwrap().unwrap();
+        let save = GameSave::read_as(File::open(path).unwrap(), SaveFormat::Json).unwrap();
+        assert_eq!(save.end_state, State::Aborted);
+        assert_eq!(save.moves, game.history());
 
-                if let Ok(mov) = res {
-                    break mov;
-                }
-                // TODO: we may only recall `think` if the error is not an io error.
-                let Err(e) = res else { unreachable!() };
-                previous_err = Some(e);
-            };
+        fs::remove_dir_all(dir).unwrap();
+    }
 
-            // we store the move if we save the games.
-            if self.settings.game_record {
-                let Some(ref mut save) = self.save else {
-                    panic!("the sttings game record is true but the path is None, it shouldn't be possible.");
-                };
-                save.push_move(mov);
-            }
+    #[test]
+    fn rendering_doesnt_change_the_game() {
+        // an endgame between two deterministic bots, few boards are drawn
+        let (board, turn) = random_positions(863, 1)
+            .into_iter()
+            .find(|(board, turn)| board.empty_count() == 6 && board.legal_moves(*turn) != 0)
+            .unwrap();
 
-            match self.make_turn(mov) {
-                Ok(()) => {}
-                Err(e @ OthelloError::IllegalMove { .. }) => {
-                    let s = &mut *self.stream.borrow_mut();
-                    s.set_color(&style::ERROR)?;
-                    writeln!(s, "{e}")?;
-                    s.reset()?;
-                }
-                Err(e) => return Err(e),
+        let play = |render| {
+            let bot = || {
+                Box::new(player::AdaptivePlayer::new(
+                    None,
+                    player::DepthSchedule::new(vec![(0, search::SearchDepth::Plies(2))]),
+                ))
             };
-        }
-        Ok(())
+            let mut game = Game::with_board(
+                board.clone(),
+                bot(),
+                bot(),
+                StandardStream::stdout(ColorChoice::Never),
+                GameSettings {
+                    render,
+                    game_record: false,
+                    saves_game_dir: None,
+                    first_player: turn,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            game.play().unwrap();
+            (game.state, game.board, game.history)
+        };
+
+        let rendered = play(true);
+        assert!(matches!(rendered.0, State::Winned { .. } | State::Draw));
+        assert_eq!(play(false), rendered);
     }
 
-    /// Post play, actions like storing the saved game.
-    // TODO: try to make it the implementation of Drop
-    pub fn post_play(self) -> Result<()> {
-        if let Some(mut save) = self.save {
-            save.end_state = self.state;
+    #[test]
+    fn force_pass_only_with_the_setting() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        assert!(matches!(
+            game.force_pass(),
+            Err(OthelloError::ForcedPassNotAllowed)
+        ));
+        assert_eq!(game.turn(), Disc::Black);
+        assert!(game.passes().is_empty());
 
-            let json_save = save.to_json();
-            let saves_dir = self.settings.saves_game_dir.clone().unwrap();
+        let mut game = test_game(
+            Board::new(),
+            GameSettings {
+                allow_forced_pass: true,
+                ..Default::default()
+            },
+        );
+        game.force_pass().unwrap();
+        assert_eq!(game.turn(), Disc::White);
+        assert_eq!(game.board, Board::new());
+        assert_eq!(game.passes(), [0]);
+        assert_eq!(
+            game.current_legal_moves,
+            Some(Board::new().legal_moves(Disc::White))
+        );
+    }
 
-            let mut filepath = self
-                .settings
-                .saves_game_dir
-                .expect("HMMMM it should really really not be None this is an error.");
+    /// A bot playing always the same move, legal or not.
+    #[derive(Debug)]
+    struct StubbornPlayer {
+        color: Disc,
+        mov: Move,
+    }
 
-            filepath.push(format!("{}.json", save.title));
+    impl Player for StubbornPlayer {
+        fn color(&self) -> Disc {
+            self.color
+        }
 
-            if !saves_dir.exists() {
-                fs::create_dir_all(saves_dir)?;
-            }
-            let mut file = File::create_new(filepath)?;
+        fn think(&self, _: &Game, _: Option<OthelloError>) -> Result<ThinkOutcome> {
+            Ok(ThinkOutcome::Move(self.mov))
+        }
 
-            file.write(json_save.as_bytes())?;
-            // write a new line otherwise on unix platform it may not be super
-            // happy.
-            file.write(b"\n")?;
+        fn name(&self) -> Option<Cow<'static, str>> {
+            None
         }
-        Ok(())
-    }
 
-    /// Call the method `think` on the current player.
-    fn player_think(&self, previous_err: Option<OthelloError>) -> Result<Move> {
-        match self.turn() {
-            Disc::Black => self.black_player.think(self, previous_err),
-            Disc::White => self.white_player.think(self, previous_err),
-            Disc::Empty => unreachable!(),
+        fn init_color(&mut self, color: Disc) {
+            self.color = color;
         }
-    }
 
-    pub fn current_player(&self) -> &dyn Player {
-        match self.turn() {
-            Disc::White => self.white_player.as_ref(),
-            Disc::Black => self.black_player.as_ref(),
-            Disc::Empty => unreachable!(),
+        fn player_type(&self) -> PlayerType {
+            PlayerType::Bot
         }
     }
 
-    #[inline]
-    #[must_use]
-    pub fn white_name(&self) -> Cow<'_, str> {
-        self.white_player.force_name()
+    #[test]
+    fn illegal_bot_moves_list_the_legal_moves() {
+        let a1 = Move::from_algebric("a1").unwrap();
+        let mut game = Game::with_board(
+            Board::new(),
+            Box::new(RandomPlayer::new(None)),
+            Box::new(StubbornPlayer {
+                color: Disc::Empty,
+                mov: a1,
+            }),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                game_record: false,
+                saves_game_dir: None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let err = game.play().unwrap_err();
+        assert!(matches!(
+            &err,
+            OthelloError::IllegalBotMove { mov, legal_moves }
+                if *mov == a1 && legal_moves.len() == 4
+        ));
+        assert_eq!(
+            err.to_string(),
+            "the bot played the illegal move a1, the legal moves were: d3, c4, f5, e6"
+        );
+        assert!(game.history().is_empty());
     }
 
-    #[inline]
-    #[must_use]
-    pub fn black_name(&self) -> Cow<'_, str> {
-        self.black_player.force_name()
+    #[test]
+    fn corners_only_hints_the_legal_corners() {
+        // Black can play a1, h1, c2 and a3
+        let board: Board = format!("-OX--XO-XO{}", "-".repeat(54)).parse().unwrap();
+        let legal_moves = board.legal_moves(Disc::Black);
+        let (a1, h1, c2, a3) = (1 << 0, 1 << 7, 1 << 10, 1 << 16);
+        assert_eq!(legal_moves, a1 | h1 | c2 | a3);
+
+        let hints = |hints: LegalMoveHints, legal_moves| {
+            hints.hinted_moves(&board, Disc::Black, legal_moves)
+        };
+        assert_eq!(hints(LegalMoveHints::All, legal_moves), Some(legal_moves));
+        assert_eq!(
+            hints(LegalMoveHints::CornersOnly, legal_moves),
+            Some(a1 | h1)
+        );
+        assert_eq!(hints(LegalMoveHints::CornersOnly, c2 | a3), Some(0));
+        assert_eq!(hints(LegalMoveHints::None, legal_moves), None);
     }
 
-    #[inline]
-    #[must_use]
-    pub fn player_name(&self) -> Cow<'_, str> {
-        match self.turn {
-            Disc::White => self.white_name(),
-            Disc::Black => self.black_name(),
-            Disc::Empty => unreachable!(),
+    #[test]
+    fn game_in_progress_is_on_the_disk() {
+        let dir = env::temp_dir().join(format!("othe-in-progress-{}", std::process::id()));
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for mov in algebric_moves(&["f5", "d6"]) {
+            game.legal_moves();
+            game.make_turn(mov).unwrap();
+            game.autosave();
         }
+
+        let path = game
+            .save
+            .as_ref()
+            .unwrap()
+            .in_progress_path(&dir, game.settings.save_format);
+        let save = GameSave::read_as(File::open(path).unwrap(), game.settings.save_format).unwrap();
+        assert_eq!(save.moves, algebric_moves(&["f5", "d6"]));
+        assert_eq!(save.end_state, State::Playing);
+
+        fs::remove_dir_all(dir).unwrap();
     }
 
-    #[inline]
-    #[must_use]
-    pub fn maybe_name(&self) -> Option<Cow<'static, str>> {
-        match self.turn {
-            Disc::White => self.white_player.name(),
-            Disc::Black => self.black_player.name(),
-            Disc::Empty => unreachable!(),
+    /// The save of a game of a few moves, nothing is written on the disk. Its
+    /// file stem isn't serialized, it's `None` like in the saves read back.
+    fn short_game_save() -> GameSave {
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(env::temp_dir()),
+                autosave: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for mov in ["f5", "d6", "c3"] {
+            game.apply_algebraic(mov).unwrap();
+        }
+        GameSave {
+            file_stem: None,
+            ..game.save.unwrap()
         }
     }
 
-    /// Renders the board game to stdout
-    pub fn render(&self, s: Option<&mut StandardStream>) -> Result<()> {
-        let mut _s = self.stream.borrow_mut();
-        let s: &mut StandardStream = s.unwrap_or(&mut *_s);
-        let Some(legal_moves) = self.current_legal_moves else {
-            return Err(OthelloError::LegalMovesNotComputed);
-        };
-
-        for row in 0..8 {
-            s.set_color(&style::BOARD_EDGES)?;
-            write!(s, "+---+---+---+---+---+---+---+---+")?;
-            s.reset()?;
+    #[test]
+    fn save_round_trips_through_a_cursor() {
+        let save = short_game_save();
+        for pretty in [false, true] {
+            let mut cursor = io::Cursor::new(Vec::new());
+            save.to_writer(&mut cursor, pretty).unwrap();
+            cursor.set_position(0);
+            assert_eq!(GameSave::from_reader(cursor).unwrap(), save);
+        }
+    }
 
-            // print the scores
-            if row == 7 {
-                let (white_score, black_score, _) = self.board.scores();
-                write!(s, "    ")?;
+    #[test]
+    fn saves_are_written_as_compact_json() {
+        let dir = env::temp_dir().join(format!("othe-compact-{}", std::process::id()));
+        let save = short_game_save();
+        let compact = save.to_json_compact();
+        assert!(!compact.contains('\n'));
+        assert_ne!(compact, save.to_json_pretty());
 
-                s.set_color(&style::BLACK_PLAYER)?;
-                write!(s, "{}", self.black_name())?;
-                s.reset()?;
-                write!(s, ": {black_score}  ")?;
+        let mut json = Vec::new();
+        save.write_as(&mut json, SaveFormat::Json).unwrap();
+        assert_eq!(String::from_utf8(json).unwrap(), compact);
 
-                s.set_color(&style::WHITE_PLAYER)?;
-                write!(s, "{}", self.white_name())?;
-                s.reset()?;
-                write!(s, ": {white_score}")?;
-            }
+        // the file is the same whatever the profile of the build
+        let path = save.write_to(&dir, SaveFormat::Json).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), compact + "\n");
 
-            writeln!(s)?;
+        fs::remove_dir_all(dir).unwrap();
+    }
 
-            for col in 0..8 {
-                let idx = row * 8 + col;
-                let is_legal_move = (1 << idx) & legal_moves != 0;
-                let disc = self.board.squares[idx];
+    #[test]
+    fn html_of_the_starting_board() {
+        let board = Board::new();
+        let html = board.to_html(Some(board.legal_moves(Disc::Black)));
+        assert_eq!(html.matches("<td ").count(), 64);
+        assert_eq!(html.matches("<th>").count(), 17);
 
-                s.set_color(&style::BOARD_EDGES)?;
-                write!(s, "|")?;
-                s.reset()?;
+        // the squares from c4 to f4 and from d5 to f5
+        let row = |n| {
+            html.lines()
+                .find(|line| line.starts_with(&format!("  <tr><th>{n}</th>")))
+                .unwrap()
+        };
+        assert!(row(4).contains(
+            "<td class=\"empty legal\"></td><td class=\"white\"></td><td class=\"black\"></td><td class=\"empty\"></td>"
+        ));
+        assert!(row(5).contains(
+            "<td class=\"black\"></td><td class=\"white\"></td><td class=\"empty legal\"></td>"
+        ));
+        assert_eq!(html.matches("legal").count(), 4);
+    }
 
-                match disc {
-                    Disc::White => {
-                        s.set_color(&style::WHITE_PLAYER)?;
-                        write!(s, " W ")?;
-                    }
-                    Disc::Black => {
-                        s.set_color(&style::BLACK_PLAYER)?;
-                        write!(s, " B ")?;
-                    }
-                    Disc::Empty if is_legal_move && self.settings.show_legal_moves => {
-                        s.set_color(&style::LEGAL_MOVE)?;
-                        write!(s, " • ")?;
-                    }
-                    Disc::Empty => write!(s, "   ")?,
-                }
-                s.reset()?;
-            }
+    #[test]
+    fn unwritable_saves_directory_is_reported() {
+        // a directory can't be created inside a file, even by root
+        let file = env::temp_dir().join(format!("othe-unwritable-{}", std::process::id()));
+        File::create(&file).unwrap();
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(file.join("saves")),
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-            s.set_color(&style::BOARD_EDGES)?;
-            write!(s, "|")?;
-            s.reset()?;
+        // the autosave is disabled after its first failure
+        game.play().unwrap();
+        assert!(!game.settings.autosave);
+        assert!(game.save.is_some());
+        assert_eq!(game.post_play().unwrap(), None);
 
-            s.set_color(&style::WHITE_BOLD)?;
-            write!(s, " {}", row + 1)?;
+        fs::remove_file(file).unwrap();
+    }
 
-            // print the score
-            if row == 6 {
-                write!(s, "  SCORES:")?;
-            }
-            s.reset()?;
+    #[test]
+    fn analysis_has_an_entry_per_move() {
+        let save = short_game_save();
+        let analysis = save.analyze(&|board, player| board.disc_diff(player) as i16);
+        assert_eq!(analysis.len(), save.moves.len());
 
-            writeln!(s)?;
+        for (ply, (entry, mov)) in analysis.iter().zip(&save.moves).enumerate() {
+            assert_eq!(entry.ply, ply);
+            assert_eq!(entry.mov, *mov);
+            assert_eq!(
+                entry.player,
+                [Disc::Black, Disc::White][ply % 2],
+                "no one passed"
+            );
+            // every move flips a single disc
+            assert_eq!(entry.swing(), 3);
         }
-        s.set_color(&style::BOARD_EDGES)?;
-        writeln!(s, "+---+---+---+---+---+---+---+---+")?;
-        s.reset()?;
+        assert_eq!((analysis[1].before, analysis[1].after), (-3, 0));
+    }
 
-        s.set_color(&style::WHITE_BOLD)?;
-        writeln!(s, "  a   b   c   d   e   f   g   h")?;
-        s.reset()?;
+    #[test]
+    fn saved_game_path_is_printed() {
+        let dir = env::temp_dir().join(format!("othe-saved-path-{}", std::process::id()));
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(dir.clone()),
+                autosave: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.play().unwrap();
+        let file_name = game.save.as_ref().unwrap().file_name(SaveFormat::Json);
 
-        Ok(())
+        let path = game.post_play().unwrap().unwrap();
+        assert_eq!(path, dir.join(&file_name));
+        let mut s = NoColor::new(Vec::new());
+        Game::render_save_path(&mut s, &path).unwrap();
+        let message = String::from_utf8(s.into_inner()).unwrap();
+        assert!(message.starts_with("Game saved to "));
+        assert!(message.trim_end().ends_with(&file_name));
+
+        // nothing is saved without the game record
+        let mut game = test_game(Board::new(), GameSettings::default());
+        game.play().unwrap();
+        assert_eq!(game.post_play().unwrap(), None);
+
+        fs::remove_dir_all(dir).unwrap();
     }
 
-    /// Compute and store the legal moves of the current player.
-    fn legal_moves(&mut self) {
-        self.current_legal_moves = Some(self.board.legal_moves(self.turn()));
+    #[test]
+    fn board_accessor_follows_the_moves() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        assert_eq!(game.board(), &Board::new());
 
-        if let Some(0) = self.current_legal_moves {
-            if self.board.legal_moves(!self.turn()) != 0 {
-                // the opponent can play, so we forfeit this turn
-                self.state = State::TurnForfeited;
-                return;
-            }
-            // No one can move this is either a draw or a win.
-            let (white, black, empty) = self.board.scores();
-            if white == black {
-                // this is a draw.
-                self.state = State::Draw;
-                return;
-            }
-            // TODO: here a simple opti is storing `white > black`
-            let winner_score = white.max(black) + empty;
-            let loser_score = white.min(black);
+        let f5 = algebric_moves(&["f5"]);
+        game.legal_moves();
+        game.make_turn(f5[0]).unwrap();
+        let expected = Board::new().apply_moves(Disc::Black, &f5).unwrap();
+        assert_eq!(game.board(), &expected[0]);
+        // f5 flipped e5
+        assert_eq!(game.board().get_disc((4, 4)), Disc::Black);
+    }
 
-            let winner_color = if white > black {
-                Disc::White
-            } else {
-                Disc::Black
-            };
+    #[test]
+    fn equal_boards_hash_the_same() {
+        let notation = format!("{0}OX------XO{0}", "-".repeat(27));
+        let boards: HashSet<Board> = [Board::new(), notation.parse().unwrap()].into();
+        assert_eq!(boards.len(), 1);
 
-            let winner_name: String = match winner_color {
-                Disc::White => self.white_name(),
-                Disc::Black => self.black_name(),
-                Disc::Empty => unreachable!(),
-            }
-            .into();
+        let moves: HashSet<Move> = algebric_moves(&["f5", "f5", "d6"]).into_iter().collect();
+        assert_eq!(moves.len(), 2);
+        let discs = HashSet::from([Disc::Black, Disc::White, Disc::Black]);
+        assert_eq!(discs.len(), 2);
+    }
 
-            self.state = State::Winned {
-                winner_name,
-                winner_color,
-                winner_score,
-                loser_score,
-            };
-        }
+    #[test]
+    fn non_interactive_replay_doesnt_wait() {
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                saves_game_dir: Some(env::temp_dir()),
+                autosave: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        game.play().unwrap();
+        let mut save = game.save.clone().unwrap();
+        save.end_state = game.state().clone();
+
+        // the standard input isn't read, the replay would wait for a key
+        // press otherwise
+        save.replay_with(StandardStream::stdout(ColorChoice::Never), false)
+            .unwrap();
+
+        save.end_state = State::Playing;
+        assert!(matches!(
+            save.replay_with(StandardStream::stdout(ColorChoice::Never), false),
+            Err(OthelloError::ReplayMismatch { .. })
+        ));
     }
 
-    #[inline]
-    pub fn moves(&self) -> u64 {
-        self.current_legal_moves.unwrap()
+    #[test]
+    fn square_info_on_the_starting_board() {
+        let mut game = test_game(Board::new(), GameSettings::default());
+        let [d3, d4] = algebric_moves(&["d3", "d4"])[..] else {
+            unreachable!()
+        };
+        assert!(matches!(
+            game.square_info(d3),
+            Err(OthelloError::LegalMovesNotComputed)
+        ));
+
+        game.legal_moves();
+        assert_eq!(
+            game.square_info(d3).unwrap(),
+            SquareInfo {
+                algebric: "d3".to_owned(),
+                disc: Disc::Empty,
+                is_legal: true,
+                // d4 is flipped
+                flips: 1 << 27,
+            }
+        );
+        assert_eq!(
+            game.square_info(d4).unwrap(),
+            SquareInfo {
+                algebric: "d4".to_owned(),
+                disc: Disc::White,
+                is_legal: false,
+                flips: 0,
+            }
+        );
     }
 }
@@ -0,0 +1,48 @@
+//! The strings shown to the players, they can be changed to translate or
+//! customize 0the.
+
+use std::borrow::Cow;
+
+/// The prompts shown to the players, the default is English.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    /// Shown after the color and the name of a human player when it's his
+    /// turn, e.g: `Black (Thibault)'s turn: `.
+    pub turn_prompt: Cow<'static, str>,
+    /// Shown after the color and the name of a human player when his opponent
+    /// offers a draw.
+    pub draw_prompt: Cow<'static, str>,
+    /// Shown between the moves when replaying a game.
+    pub continue_prompt: Cow<'static, str>,
+    /// Shown by the CLI when waiting for a command.
+    pub command_prompt: Cow<'static, str>,
+    /// Shown to a human player far behind, see
+    /// [`GameSettings::resign_threshold`].
+    ///
+    /// [`GameSettings::resign_threshold`]: crate::GameSettings::resign_threshold
+    pub resign_prompt: Cow<'static, str>,
+    /// Shown when a human player types `?` or `help` instead of his move.
+    pub game_help: Cow<'static, str>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Messages {
+            turn_prompt: Cow::Borrowed("'s turn: "),
+            draw_prompt: Cow::Borrowed(
+                ", your opponent offers a draw, do you accept? (`Yes` or `No`) ",
+            ),
+            continue_prompt: Cow::Borrowed("Press any key to continue..."),
+            command_prompt: Cow::Borrowed("Command (h for help): "),
+            resign_prompt: Cow::Borrowed("You're far behind, resign? (y/n) "),
+            game_help: Cow::Borrowed(
+                "\
+Commands:
+    <move>       Play your move, e.g: `d3`
+    draw         Offer a draw to your opponent
+    quit, menu   Leave the game and go back to the menu
+    ?, help      Show this help",
+            ),
+        }
+    }
+}
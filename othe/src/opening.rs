@@ -0,0 +1,204 @@
+//! Recognition of the well known Othello openings.
+//!
+//! The openings are written from the point of view of a game that starts with
+//! `f5`, the games starting with one of the three other first moves are
+//! transformed with the symmetry of the board that brings their first move to
+//! `f5`.
+
+use std::collections::BTreeMap;
+
+use crate::{Disc, GameSave, Move, State};
+
+/// Known openings with their moves in algebric notation, the longest matching
+/// opening wins so the more specific ones can share a prefix with the generic
+/// ones.
+pub const OPENINGS: &[(&str, &[&str])] = &[
+    ("Perpendicular", &["f5", "d6"]),
+    ("Diagonal", &["f5", "f6"]),
+    ("Parallel", &["f5", "f4"]),
+    ("Tiger", &["f5", "d6", "c3", "d3", "c4"]),
+    ("Cow", &["f5", "d6", "c5"]),
+];
+
+/// The symmetries of the board that keep the starting position, they bring
+/// the four first moves on each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    Identity,
+    /// Along the a1-h8 diagonal.
+    Transpose,
+    /// Along the a8-h1 diagonal.
+    AntiTranspose,
+    /// Rotation of 180 degrees.
+    Rotate180,
+}
+
+impl Symmetry {
+    /// Return the symmetry that brings the `first` move of a game to `f5`,
+    /// the identity if it isn't one of the four first moves.
+    pub fn to_f5(first: Option<Move>) -> Symmetry {
+        match first.map(|mov| mov.to_algebric()) {
+            Some(first) if first == "e6" => Symmetry::Transpose,
+            Some(first) if first == "d3" => Symmetry::AntiTranspose,
+            Some(first) if first == "c4" => Symmetry::Rotate180,
+            _ => Symmetry::Identity,
+        }
+    }
+
+    /// Return the image of `mov` by the symmetry.
+    pub fn apply(self, Move { col, row }: Move) -> Move {
+        match self {
+            Symmetry::Identity => Move { col, row },
+            Symmetry::Transpose => Move { col: row, row: col },
+            Symmetry::AntiTranspose => Move {
+                col: 7 - row,
+                row: 7 - col,
+            },
+            Symmetry::Rotate180 => Move {
+                col: 7 - col,
+                row: 7 - row,
+            },
+        }
+    }
+}
+
+/// Transform the moves of a game so its first move is `f5`, the other first
+/// moves are symmetric to it. The games that only differ by a symmetry of
+/// the board have the same canonical moves.
+pub fn normalize(moves: &[Move]) -> Vec<Move> {
+    let symmetry = Symmetry::to_f5(moves.first().copied());
+    moves.iter().map(|&mov| symmetry.apply(mov)).collect()
+}
+
+/// Return the name of the opening played in `moves`, if it's a known one.
+pub fn opening_name(moves: &[Move]) -> Option<&'static str> {
+    let moves = normalize(moves);
+
+    OPENINGS
+        .iter()
+        .filter(|(_, opening)| {
+            opening.len() <= moves.len()
+                && opening
+                    .iter()
+                    .zip(&moves)
+                    .all(|(algebric, mov)| *algebric == mov.to_algebric())
+        })
+        .max_by_key(|(_, opening)| opening.len())
+        .map(|(name, _)| *name)
+}
+
+/// Results of the finished games played with an opening, see
+/// [`opening_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpeningStats {
+    pub games: u32,
+    pub black_wins: u32,
+    pub white_wins: u32,
+    pub draws: u32,
+}
+
+impl OpeningStats {
+    /// Ratio of the games won by black, between 0 and 1.
+    pub fn black_win_rate(&self) -> f64 {
+        self.black_wins as f64 / self.games as f64
+    }
+
+    /// Ratio of the games won by white, between 0 and 1.
+    pub fn white_win_rate(&self) -> f64 {
+        self.white_wins as f64 / self.games as f64
+    }
+}
+
+/// Tally the results of the finished games by their opening, the first two
+/// moves normalized with [`normalize`] and written in algebric notation, e.g:
+/// `"f5 d6"`. The aborted games, the games shorter than two moves and the
+/// games that didn't start from the standard position with Black first are
+/// skipped, see [`GameSave::has_standard_start`].
+pub fn opening_stats<'a>(
+    saves: impl IntoIterator<Item = &'a GameSave>,
+) -> BTreeMap<String, OpeningStats> {
+    let mut stats = BTreeMap::<String, OpeningStats>::new();
+
+    for save in saves {
+        if save.moves.len() < 2 || !save.has_standard_start() {
+            continue;
+        }
+
+        let stat = match &save.end_state {
+            State::Winned { .. } | State::Draw | State::TimedOut { .. } => {
+                let opening = normalize(&save.moves[..2])
+                    .iter()
+                    .map(|mov| mov.to_algebric())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                stats.entry(opening).or_default()
+            }
+            _ => continue,
+        };
+
+        stat.games += 1;
+        match save.end_state {
+            State::Winned {
+                winner_color: Disc::Black,
+                ..
+            }
+            | State::TimedOut { loser: Disc::White } => stat.black_wins += 1,
+            State::Winned { .. } | State::TimedOut { .. } => stat.white_wins += 1,
+            _ => stat.draws += 1,
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, StartKind};
+
+    #[test]
+    fn openings_are_tallied_on_standard_starts_only() {
+        // black wipes white out after d3 c3, the opening f5 f6 once normalized
+        let save = GameSave::from_transcript("wipeout".to_owned(), "d3c3b3d2e1d6d7e3f4").unwrap();
+        let mut white_first = save.clone();
+        white_first.first_player = Disc::White;
+        let mut parallel = save.clone();
+        parallel.start = Some(Board::with_start(StartKind::Parallel));
+
+        let stats = opening_stats([&save, &save, &white_first, &parallel]);
+        assert_eq!(
+            stats.into_iter().collect::<Vec<_>>(),
+            [(
+                "f5 f6".to_owned(),
+                OpeningStats {
+                    games: 2,
+                    black_wins: 2,
+                    white_wins: 0,
+                    draws: 0,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn symmetric_games_normalize_identically() {
+        let tiger: Vec<Move> = ["f5", "d6", "c3", "d3", "c4"]
+            .iter()
+            .map(|mov| Move::from_algebric(mov).unwrap())
+            .collect();
+
+        let mut first_moves = Vec::new();
+        for symmetry in [
+            Symmetry::Identity,
+            Symmetry::Transpose,
+            Symmetry::AntiTranspose,
+            Symmetry::Rotate180,
+        ] {
+            let game: Vec<Move> = tiger.iter().map(|&mov| symmetry.apply(mov)).collect();
+            first_moves.push(game[0].to_algebric());
+            assert_eq!(normalize(&game), tiger);
+            assert_eq!(opening_name(&game), Some("Tiger"));
+        }
+        assert_eq!(first_moves, ["f5", "e6", "d3", "c4"]);
+    }
+}
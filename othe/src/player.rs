@@ -1,13 +1,19 @@
-use std::fmt::Debug;
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Debug};
 use std::io::{Read, Write};
-use std::sync::{Arc, Mutex};
+use std::rc::Rc;
+use std::sync::Arc;
 use std::{borrow::Cow, io};
 
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng};
 use serde::{Deserialize, Serialize};
 use termcolor::WriteColor;
 
-use crate::{bitfield_to_indexes, style, Disc, Game, Move, OthelloError, Result};
+use crate::{
+    bits, prompt, read_input,
+    search::{self, SearchDepth},
+    style, Board, Disc, Game, Move, OthelloError, Result, State,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerType {
@@ -17,6 +23,26 @@ pub enum PlayerType {
     Bot,
 }
 
+/// What a player decided to do during his turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThinkOutcome {
+    /// Play this move.
+    Move(Move),
+    /// Pass the turn without placing a disc although the player can move, see
+    /// [`Game::force_pass`].
+    Pass,
+    /// Offer a draw to the opponent, the game ends in a draw if he accepts.
+    OfferDraw,
+    /// Abort the game and go back to the menu.
+    Quit,
+    /// The player ran out of time, he loses the game. The [`Game`] checks the
+    /// clocks of its [`TimeControl`] itself, it is used by the replay of the
+    /// games lost on time.
+    ///
+    /// [`TimeControl`]: crate::TimeControl
+    TimedOut,
+}
+
 /// A player of the Othello Game, it may be Human, a bot like MinMax, AlphaBeta
 /// pruning, Monte Carlo Tree Search, a fancy powerful AI..
 pub trait Player: Debug {
@@ -25,8 +51,17 @@ pub trait Player: Debug {
 
     /// This function is called when it is the turn of this player, or when the
     /// previous call to this function resulted in a error (`err` arg) from the
-    /// player, like illegal move etc..
-    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<Move>;
+    /// player, like illegal move etc.. The legal moves are computed before,
+    /// see [`Game::legal_move_list`]. A bot playing an illegal move stops the
+    /// game with [`OthelloError::IllegalBotMove`].
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome>;
+
+    /// This function is called when the opponent offers a draw, return `true`
+    /// to accept it and end the game. Decline by default.
+    fn accept_draw(&self, game: &Game) -> Result<bool> {
+        let _ = game;
+        Ok(false)
+    }
 
     /// Return the name of the player.
     fn name(&self) -> Option<Cow<'static, str>>;
@@ -34,6 +69,12 @@ pub trait Player: Debug {
     /// Init the player color if the player stores its disc color.
     fn init_color(&mut self, color: Disc);
 
+    /// Clear the state the player kept during the previous game, e.g: a
+    /// search tree, it is called when a game is created before the color is
+    /// initialized. A player that stores its color must clear it so it can
+    /// play the other color. Does nothing by default.
+    fn reset(&mut self) {}
+
     /// Return the name of the player and if he have no name, its color.
     fn force_name(&self) -> Cow<'_, str> {
         match self.name() {
@@ -58,10 +99,38 @@ pub trait Player: Debug {
     }
 }
 
+/// Reads a line after showing its prompt, e.g: a line editor with history.
+/// It returns `None` at the end of the input.
+#[derive(Clone)]
+pub struct LineReader(Rc<RefCell<ReadLineFn>>);
+
+type ReadLineFn = dyn FnMut(&str) -> io::Result<Option<String>>;
+
+impl LineReader {
+    pub fn new(read_line: impl FnMut(&str) -> io::Result<Option<String>> + 'static) -> LineReader {
+        LineReader(Rc::new(RefCell::new(read_line)))
+    }
+
+    /// Show the `prompt` and read a line.
+    pub fn read_line(&self, prompt: &str) -> io::Result<Option<String>> {
+        (self.0.borrow_mut())(prompt)
+    }
+}
+
+impl Debug for LineReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LineReader")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HumanPlayer {
     color: Disc,
     name: Option<String>,
+    /// Reads the moves instead of the standard input.
+    line_reader: Option<LineReader>,
+    /// The resignation is only suggested once per game.
+    resign_suggested: Cell<bool>,
 }
 
 impl HumanPlayer {
@@ -71,6 +140,29 @@ impl HumanPlayer {
         HumanPlayer {
             color: Disc::Empty,
             name,
+            line_reader: None,
+            resign_suggested: Cell::new(false),
+        }
+    }
+
+    /// Read the moves with the `line_reader`, the prompt is passed to it
+    /// instead of being written to the stream of the game.
+    pub fn with_line_reader(mut self, line_reader: LineReader) -> HumanPlayer {
+        self.line_reader = Some(line_reader);
+        self
+    }
+
+    /// Show the `msg` and read a line, with the line reader if there is one.
+    fn read_line(&self, s: &mut impl Write, msg: &str) -> io::Result<Option<String>> {
+        match &self.line_reader {
+            Some(line_reader) => {
+                s.flush()?;
+                line_reader.read_line(msg)
+            }
+            None => {
+                prompt(s, msg)?;
+                read_input(&mut io::stdin().lock())
+            }
         }
     }
 }
@@ -80,7 +172,7 @@ impl Player for HumanPlayer {
         self.color
     }
 
-    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<Move> {
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome> {
         let s = &mut *game.stream.borrow_mut();
 
         if let Some(err) = err {
@@ -89,20 +181,57 @@ impl Player for HumanPlayer {
             s.reset()?;
         }
 
-        let mut mov_str = String::with_capacity(3);
+        if let Some(threshold) = game.settings.resign_threshold {
+            if !self.resign_suggested.get()
+                && game.should_suggest_resign(&search::evaluate, threshold)
+            {
+                self.resign_suggested.set(true);
+                let answer = self.read_line(s, &game.settings.messages.resign_prompt)?;
+                if answer.is_some_and(|answer| answer.to_lowercase().trim() == "y") {
+                    return Ok(ThinkOutcome::Quit);
+                }
+            }
+        }
 
-        write!(s, "{}", game.turn())?;
+        let mut turn_prompt = game.turn().to_string();
         if let Some(name) = self.name() {
-            write!(s, " ({})", name)?;
+            turn_prompt += &format!(" ({})", name);
         }
-        write!(s, "'s turn: ")?;
+        turn_prompt += &game.settings.messages.turn_prompt;
+
+        let mov_str = loop {
+            let Some(line) = self.read_line(s, &turn_prompt)? else {
+                // end of the input, no more moves can be read
+                writeln!(s)?;
+                return Ok(ThinkOutcome::Quit);
+            };
+
+            match line.trim() {
+                // the help doesn't use the turn, ask the move again
+                "?" | "help" => writeln!(s, "{}", game.settings.messages.game_help)?,
+                cmd => break cmd.to_owned(),
+            }
+        };
 
-        s.flush()?;
-        io::stdin().read_line(&mut mov_str)?;
-        // pop the newline char at the end
-        mov_str.pop();
+        match mov_str.as_str() {
+            "draw" => Ok(ThinkOutcome::OfferDraw),
+            "quit" | "menu" => Ok(ThinkOutcome::Quit),
+            mov => Move::from_algebric(mov).map(ThinkOutcome::Move),
+        }
+    }
+
+    fn accept_draw(&self, game: &Game) -> Result<bool> {
+        let s = &mut *game.stream.borrow_mut();
 
-        Move::from_algebric(&mov_str)
+        write!(s, "{}", self.color)?;
+        if let Some(name) = self.name() {
+            write!(s, " ({})", name)?;
+        }
+        let answer = self
+            .read_line(s, &game.settings.messages.draw_prompt)?
+            .unwrap_or_default();
+
+        Ok(answer.to_lowercase().trim() == "yes")
     }
 
     fn name(&self) -> Option<Cow<'static, str>> {
@@ -115,6 +244,11 @@ impl Player for HumanPlayer {
         self.color = color;
     }
 
+    fn reset(&mut self) {
+        self.color = Disc::Empty;
+        self.resign_suggested.set(false);
+    }
+
     #[inline]
     fn player_type(&self) -> PlayerType {
         PlayerType::Human
@@ -124,11 +258,58 @@ impl Player for HumanPlayer {
 #[derive(Debug, Clone)]
 pub struct RandomPlayer {
     color: Disc,
+    name: Option<String>,
+    /// Probability to play a corner when one is legal.
+    corner_probability: f64,
+}
+
+impl RandomPlayer {
+    /// Create a random bot, without name it is called `Random Bot`.
+    pub fn new(name: impl Into<Option<String>>) -> RandomPlayer {
+        let name = name.into().filter(|n| !n.is_empty());
+
+        RandomPlayer {
+            color: Disc::Empty,
+            name,
+            corner_probability: 0.0,
+        }
+    }
+
+    /// Play a corner with the `probability` when one is legal, the other
+    /// moves are still chosen at random.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the probability isn't between 0 and 1.
+    pub fn prefer_corners(mut self, probability: f64) -> RandomPlayer {
+        assert!((0.0..=1.0).contains(&probability));
+        self.corner_probability = probability;
+        self
+    }
+
+    /// Pick a move among the `legal_moves` bitfield, with the bias toward
+    /// the corners.
+    fn pick_move(&self, legal_moves: u64, rng: &mut impl Rng) -> u8 {
+        let corners = legal_moves & Board::CORNERS;
+        let others = legal_moves & !Board::CORNERS;
+
+        let moves = if corners != 0 && (others == 0 || rng.gen_bool(self.corner_probability)) {
+            corners
+        } else if self.corner_probability > 0.0 {
+            others
+        } else {
+            legal_moves
+        };
+
+        // it's safe to unwrap, it only return `None` if there is no legal move
+        // and we know for a fact there is one because we can play
+        bits(moves).choose(rng).unwrap()
+    }
 }
 
 impl Default for RandomPlayer {
     fn default() -> Self {
-        RandomPlayer { color: Disc::Empty }
+        RandomPlayer::new(None)
     }
 }
 
@@ -137,7 +318,7 @@ impl Player for RandomPlayer {
         self.color
     }
 
-    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<Move> {
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome> {
         // ensure there is no error(s).
         assert!(err.is_none());
 
@@ -145,19 +326,124 @@ impl Player for RandomPlayer {
             return Err(OthelloError::LegalMovesNotComputed);
         };
 
-        let legal_moves = bitfield_to_indexes(legal_moves);
-
         let mut rand = rand::thread_rng();
 
-        // it's safe to unwrap, it only return `None` if the vector is empty
-        // and we know for a fact he is not because we can play
-        Ok(Move::from_idx(
-            *legal_moves.iter().choose(&mut rand).unwrap(),
+        Ok(ThinkOutcome::Move(Move::from_idx(
+            self.pick_move(legal_moves, &mut rand),
+        )))
+    }
+
+    fn name(&self) -> Option<Cow<'static, str>> {
+        match &self.name {
+            Some(name) => Some(Cow::Owned(name.clone())),
+            None => Some(Cow::Borrowed("Random Bot")),
+        }
+    }
+
+    fn init_color(&mut self, color: Disc) {
+        assert_eq!(self.color, Disc::Empty);
+        assert_ne!(color, Disc::Empty);
+        self.color = color;
+    }
+
+    fn reset(&mut self) {
+        self.color = Disc::Empty;
+    }
+
+    #[inline]
+    fn player_type(&self) -> PlayerType {
+        PlayerType::Bot
+    }
+}
+
+/// The depth of the search depending on the number of empty squares, see
+/// [`AdaptivePlayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthSchedule {
+    /// `(min_empties, depth)` sorted by decreasing `min_empties`, the depth of
+    /// the first entry with at most as many empty squares as the board is
+    /// used.
+    entries: Vec<(u8, SearchDepth)>,
+}
+
+impl DepthSchedule {
+    /// Create a schedule from `(min_empties, depth)` entries, e.g:
+    /// `[(40, Plies(2)), (0, Exact)]` searches 2 plies with 40 empty squares
+    /// or more and solves the end of the game below.
+    pub fn new(mut entries: Vec<(u8, SearchDepth)>) -> DepthSchedule {
+        entries.sort_by(|(a, _), (b, _)| b.cmp(a));
+        DepthSchedule { entries }
+    }
+
+    /// Return the depth of the search on a board with `empties` empty
+    /// squares, the entry with the fewest empty squares is used if none
+    /// matches.
+    pub fn depth(&self, empties: u8) -> SearchDepth {
+        self.entries
+            .iter()
+            .find(|(min_empties, _)| empties >= *min_empties)
+            .or(self.entries.last())
+            .map_or(SearchDepth::Plies(1), |&(_, depth)| depth)
+    }
+}
+
+impl Default for DepthSchedule {
+    /// Shallow in the opening, deeper in the midgame and exact with 12 empty
+    /// squares or less.
+    fn default() -> Self {
+        DepthSchedule::new(vec![
+            (44, SearchDepth::Plies(3)),
+            (13, SearchDepth::Plies(5)),
+            (0, SearchDepth::Exact),
+        ])
+    }
+}
+
+/// A bot searching the best move, deeper as the game advances so its moves
+/// stay fast.
+#[derive(Debug, Clone)]
+pub struct AdaptivePlayer {
+    color: Disc,
+    name: Option<String>,
+    schedule: DepthSchedule,
+}
+
+impl AdaptivePlayer {
+    /// Create an adaptive bot, without name it is called `Adaptive Bot`.
+    pub fn new(name: impl Into<Option<String>>, schedule: DepthSchedule) -> AdaptivePlayer {
+        let name = name.into().filter(|n| !n.is_empty());
+
+        AdaptivePlayer {
+            color: Disc::Empty,
+            name,
+            schedule,
+        }
+    }
+}
+
+impl Player for AdaptivePlayer {
+    fn color(&self) -> Disc {
+        self.color
+    }
+
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome> {
+        // ensure there is no error(s).
+        assert!(err.is_none());
+
+        let depth = self.schedule.depth(game.board.empty_count());
+
+        // it's safe to unwrap, we know for a fact there is a legal move
+        // because we can play
+        Ok(ThinkOutcome::Move(
+            search::best_move(&game.board, self.color, depth).unwrap(),
         ))
     }
 
     fn name(&self) -> Option<Cow<'static, str>> {
-        Some(Cow::Borrowed("Random Bot"))
+        match &self.name {
+            Some(name) => Some(Cow::Owned(name.clone())),
+            None => Some(Cow::Borrowed("Adaptive Bot")),
+        }
     }
 
     fn init_color(&mut self, color: Disc) {
@@ -166,19 +452,163 @@ impl Player for RandomPlayer {
         self.color = color;
     }
 
+    fn reset(&mut self) {
+        self.color = Disc::Empty;
+    }
+
+    #[inline]
+    fn player_type(&self) -> PlayerType {
+        PlayerType::Bot
+    }
+}
+
+/// A bot keeping the games close, it searches shallower when it is ahead and
+/// deeper when it is behind, e.g: to play against beginners.
+#[derive(Debug, Clone)]
+pub struct BalancedPlayer {
+    color: Disc,
+    name: Option<String>,
+    min_plies: u8,
+    max_plies: u8,
+    margin: i32,
+}
+
+impl BalancedPlayer {
+    /// The default difference of discs where the depth of the search reaches
+    /// its minimum or its maximum, see [`BalancedPlayer::with_margin`].
+    pub const DEFAULT_MARGIN: i32 = 16;
+
+    /// Create a balanced bot searching between `min_plies` and `max_plies`,
+    /// without name it is called `Balanced Bot`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `min_plies` is zero or greater than `max_plies`.
+    #[track_caller]
+    pub fn new(name: impl Into<Option<String>>, min_plies: u8, max_plies: u8) -> BalancedPlayer {
+        assert!(
+            (1..=max_plies).contains(&min_plies),
+            "the depths must be at least one ply and the minimum can't be greater than the maximum"
+        );
+        let name = name.into().filter(|n| !n.is_empty());
+
+        BalancedPlayer {
+            color: Disc::Empty,
+            name,
+            min_plies,
+            max_plies,
+            margin: BalancedPlayer::DEFAULT_MARGIN,
+        }
+    }
+
+    /// Search `min_plies` when ahead by `margin` discs or more and
+    /// `max_plies` when behind by as many, the depth is in between otherwise.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `margin` isn't positive.
+    #[track_caller]
+    pub fn with_margin(mut self, margin: i32) -> BalancedPlayer {
+        assert!(margin > 0, "the margin must be positive");
+        self.margin = margin;
+        self
+    }
+
+    /// Return the number of plies searched by `player` on the `board`,
+    /// depending on his difference of discs, see [`Board::disc_diff`].
+    pub fn depth(&self, board: &Board, player: Disc) -> u8 {
+        let diff = board.disc_diff(player).clamp(-self.margin, self.margin);
+        let range = (self.max_plies - self.min_plies) as i32;
+        // from 0 when far ahead to `range` when far behind, rounded
+        let extra = ((self.margin - diff) * range + self.margin) / (2 * self.margin);
+        self.min_plies + extra as u8
+    }
+}
+
+impl Player for BalancedPlayer {
+    fn color(&self) -> Disc {
+        self.color
+    }
+
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome> {
+        // ensure there is no error(s).
+        assert!(err.is_none());
+
+        let depth = SearchDepth::Plies(self.depth(&game.board, self.color));
+
+        // it's safe to unwrap, we know for a fact there is a legal move
+        // because we can play
+        Ok(ThinkOutcome::Move(
+            search::best_move(&game.board, self.color, depth).unwrap(),
+        ))
+    }
+
+    fn name(&self) -> Option<Cow<'static, str>> {
+        match &self.name {
+            Some(name) => Some(Cow::Owned(name.clone())),
+            None => Some(Cow::Borrowed("Balanced Bot")),
+        }
+    }
+
+    fn init_color(&mut self, color: Disc) {
+        assert_eq!(self.color, Disc::Empty);
+        assert_ne!(color, Disc::Empty);
+        self.color = color;
+    }
+
+    fn reset(&mut self) {
+        self.color = Disc::Empty;
+    }
+
     #[inline]
     fn player_type(&self) -> PlayerType {
         PlayerType::Bot
     }
 }
 
+/// The bots of 0the, see [`Game::human_vs_bot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinBot {
+    /// [`RandomPlayer`]
+    Random,
+    /// [`AdaptivePlayer`] searching always at this number of plies.
+    AlphaBeta(u8),
+    /// [`AdaptivePlayer`] with this depth schedule.
+    Adaptive(DepthSchedule),
+    /// [`BalancedPlayer`] searching between these minimum and maximum numbers
+    /// of plies.
+    Balanced(u8, u8),
+}
+
+impl BuiltinBot {
+    /// Create the player of the bot, with its default name.
+    pub fn into_player(self) -> Box<dyn Player> {
+        match self {
+            BuiltinBot::Random => Box::new(RandomPlayer::default()),
+            BuiltinBot::AlphaBeta(plies) => Box::new(AdaptivePlayer::new(
+                None,
+                DepthSchedule::new(vec![(0, SearchDepth::Plies(plies))]),
+            )),
+            BuiltinBot::Adaptive(schedule) => Box::new(AdaptivePlayer::new(None, schedule)),
+            BuiltinBot::Balanced(min_plies, max_plies) => {
+                Box::new(BalancedPlayer::new(None, min_plies, max_plies))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ReplayPlayer {
-    pub(crate) moves: Arc<Mutex<Vec<Move>>>,
-    pub(crate) move_idx: Arc<Mutex<usize>>,
+    /// The moves and the passes of the recorded game, with all its passes,
+    /// `None` is a pass.
+    pub(crate) entries: Arc<Vec<Option<Move>>>,
     pub(crate) color: Disc,
     pub(crate) player_type: PlayerType,
     pub(crate) name: Option<Cow<'static, str>>,
+    /// The state at the end of the recorded game.
+    pub(crate) end_state: State,
+    /// Do we wait for a key press before each move?
+    pub(crate) interactive: bool,
 }
 
 impl Player for ReplayPlayer {
@@ -186,24 +616,44 @@ impl Player for ReplayPlayer {
         self.color
     }
 
-    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<Move> {
+    fn think(&self, game: &Game, err: Option<OthelloError>) -> Result<ThinkOutcome> {
         // ensure there is no error(s).
         assert!(err.is_none());
 
-        // it shouldn't panic because the players move one after the other
-        let mut idx = self.move_idx.lock().unwrap();
-        let mov = self.moves.lock().unwrap()[*idx];
-        *idx += 1;
+        // the forfeited turns are passed by the game without asking the
+        // player, they are in the passes of the game too.
+        let idx = game.history().len() + game.passes().len();
+        let Some(&entry) = self.entries.get(idx) else {
+            // the recorded game ended before the board was full, it was either
+            // aborted, lost on time or the players agreed to a draw.
+            return Ok(match self.end_state {
+                State::TimedOut { loser } if loser == self.color => ThinkOutcome::TimedOut,
+                State::Draw => ThinkOutcome::OfferDraw,
+                _ => ThinkOutcome::Quit,
+            });
+        };
+        let outcome = match entry {
+            Some(mov) => ThinkOutcome::Move(mov),
+            None => ThinkOutcome::Pass,
+        };
+
+        if !self.interactive {
+            return Ok(outcome);
+        }
 
         // Prompt the user
         let mut s = game.stream.borrow_mut();
-        write!(s, "Press any key to continue...")?;
-        s.flush().unwrap();
+        prompt(&mut *s, &game.settings.messages.continue_prompt)?;
 
         // Wait for input
         let _ = io::stdin().read(&mut [0u8])?;
 
-        Ok(mov)
+        Ok(outcome)
+    }
+
+    fn accept_draw(&self, _: &Game) -> Result<bool> {
+        // the draw is only offered if it was accepted in the recorded game
+        Ok(true)
     }
 
     fn name(&self) -> Option<Cow<'static, str>> {
@@ -223,3 +673,304 @@ impl Player for ReplayPlayer {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::TurnOutcome;
+
+    /// Play `games` random games and check the move generation: every move
+    /// [`RandomPlayer`] picks among the legal moves must be legal and every
+    /// game must end with no player able to move. The games are seeded with
+    /// `seed` so a failure can be reproduced, half of them prefer the corners.
+    ///
+    /// It fails with [`OthelloError::IllegalMoveInSequence`] at the first
+    /// illegal move and panics if a game doesn't end like an Othello game
+    /// should.
+    fn validate_random_play(seed: u64, games: usize) -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for game in 0..games {
+            let player =
+                RandomPlayer::new(None).prefer_corners(if game % 2 == 0 { 0.0 } else { 0.5 });
+            let mut board = Board::new();
+            let mut turn = Disc::Black;
+            let mut index = 0;
+            loop {
+                let legal_moves = match board.terminal_or_pass(turn) {
+                    TurnOutcome::Continue { legal_moves } => legal_moves,
+                    TurnOutcome::Pass { .. } => {
+                        turn = !turn;
+                        continue;
+                    }
+                    TurnOutcome::GameOver(_) => break,
+                };
+
+                let mov = Move::from_idx(player.pick_move(legal_moves, &mut rng));
+                if !board.is_legal(turn, mov) {
+                    return Err(OthelloError::IllegalMoveInSequence { index, mov });
+                }
+                board.apply_move(turn, mov);
+                turn = !turn;
+                index += 1;
+            }
+
+            assert!(
+                board.is_terminal(),
+                "the game {game} stopped before its end"
+            );
+            assert_eq!(
+                board.discs_placed() as usize,
+                index + 4,
+                "every move of the game {game} must place one disc"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn random_play_is_legal() {
+        for seed in [0, 1, 42, 2024] {
+            validate_random_play(seed, 100).unwrap();
+        }
+    }
+
+    #[test]
+    fn depth_schedule_solves_the_endgame() {
+        let schedule = DepthSchedule::default();
+        assert_eq!(schedule.depth(50), SearchDepth::Plies(3));
+        assert_eq!(schedule.depth(20), SearchDepth::Plies(5));
+        assert_eq!(schedule.depth(10), SearchDepth::Exact);
+
+        // below the last entry the deepest search is still used
+        let schedule =
+            DepthSchedule::new(vec![(40, SearchDepth::Plies(2)), (10, SearchDepth::Exact)]);
+        assert_eq!(schedule.depth(5), SearchDepth::Exact);
+    }
+
+    /// A line reader that answers with the `lines`, then reaches the end of
+    /// the input.
+    fn scripted_reader(lines: &[&str]) -> LineReader {
+        let mut lines = lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .into_iter();
+        LineReader::new(move |_| Ok(lines.next()))
+    }
+
+    #[test]
+    fn draw_offers_are_answered_with_the_line_reader() {
+        use termcolor::{ColorChoice, StandardStream};
+
+        use crate::{Game, GameSettings, State};
+
+        for (answer, state) in [("yes", State::Draw), ("no", State::Aborted)] {
+            let black = HumanPlayer::new(None).with_line_reader(scripted_reader(&["draw"]));
+            let white = HumanPlayer::new(None).with_line_reader(scripted_reader(&[answer]));
+            let mut game = Game::new(
+                Box::new(white),
+                Box::new(black),
+                StandardStream::stdout(ColorChoice::Never),
+                GameSettings {
+                    render: false,
+                    game_record: false,
+                    saves_game_dir: None,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            // a declined draw asks the move again, the input of black is over
+            game.play().unwrap();
+            assert_eq!(game.state(), &state);
+        }
+    }
+
+    #[test]
+    fn adaptive_player_solves_the_endgame() {
+        use std::str::FromStr;
+
+        use termcolor::{ColorChoice, StandardStream};
+
+        use crate::{Board, Game, GameSettings};
+
+        // with 10 empty squares Black wins by 8 with c5, the five plies search
+        // prefers c2 that loses by 8
+        let board =
+            Board::from_str("O-OOOO--OO-OXO--OXOXXOXXOOXOXOXXOX-XOXXXXXXXXO-X-XXXXXOXXXXXXXX-")
+                .unwrap();
+        let c5 = Move::from_algebric("c5").unwrap();
+        assert_eq!(
+            search::best_move(&board, Disc::Black, SearchDepth::Plies(5)),
+            Some(Move::from_algebric("c2").unwrap())
+        );
+
+        let game = Game::with_board(
+            board,
+            Box::new(RandomPlayer::new(None)),
+            Box::new(RandomPlayer::new(None)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                game_record: false,
+                saves_game_dir: None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut player = AdaptivePlayer::new(None, DepthSchedule::default());
+        player.init_color(Disc::Black);
+        assert_eq!(player.think(&game, None).unwrap(), ThinkOutcome::Move(c5));
+    }
+
+    #[test]
+    fn balanced_depth_stays_within_its_bounds() {
+        use std::str::FromStr;
+
+        let player = BalancedPlayer::new(None, 1, 5);
+        // even, the depth is in the middle
+        assert_eq!(player.depth(&Board::new(), Disc::Black), 3);
+        // Black is far ahead
+        let board = Board::from_str(&format!("{}O{}", "X".repeat(40), "-".repeat(23))).unwrap();
+        assert_eq!(player.depth(&board, Disc::Black), 1);
+        assert_eq!(player.depth(&board, Disc::White), 5);
+
+        let mut rng = StdRng::seed_from_u64(908);
+        for margin in [1, 5, BalancedPlayer::DEFAULT_MARGIN] {
+            let player = BalancedPlayer::new(None, 2, 4).with_margin(margin);
+            let mut board = Board::new();
+            let mut turn = Disc::Black;
+            while !board.is_terminal() {
+                for color in [Disc::Black, Disc::White] {
+                    assert!((2..=4).contains(&player.depth(&board, color)));
+                }
+                let legal_moves = board.legal_moves(turn);
+                if legal_moves != 0 {
+                    let mov = RandomPlayer::new(None).pick_move(legal_moves, &mut rng);
+                    board.apply_move(turn, Move::from_idx(mov));
+                }
+                turn = !turn;
+            }
+        }
+    }
+
+    #[test]
+    fn named_bots_keep_their_name() {
+        let mut named = RandomPlayer::new("Corner Hunter".to_owned());
+        named.init_color(Disc::Black);
+        let mut unnamed = RandomPlayer::new(String::new());
+        unnamed.init_color(Disc::White);
+        assert_eq!(named.force_name(), "Corner Hunter");
+        assert_eq!(unnamed.force_name(), "Random Bot");
+
+        let save = crate::GameSave::new("Bots".to_owned(), &named, &unnamed);
+        assert_eq!(save.black_name.as_deref(), Some("Corner Hunter"));
+        assert_eq!(save.white_name.as_deref(), Some("Random Bot"));
+    }
+
+    #[test]
+    fn corners_are_picked_with_their_probability() {
+        let idx = |algebric| Move::from_algebric(algebric).unwrap().into_idx() as u8;
+        let legal_moves = [idx("a1"), idx("d3"), idx("c4")]
+            .iter()
+            .fold(0, |moves, &idx| moves | 1 << idx);
+
+        let mut rng = StdRng::seed_from_u64(886);
+        for (probability, expected) in [(0.0, 1.0 / 3.0), (0.8, 0.8), (1.0, 1.0)] {
+            let player = RandomPlayer::new(None).prefer_corners(probability);
+            let corners = (0..10_000)
+                .filter(|_| player.pick_move(legal_moves, &mut rng) == idx("a1"))
+                .count();
+            let frequency = corners as f64 / 10_000.0;
+            assert!(
+                (frequency - expected).abs() < 0.02,
+                "the corner was picked {frequency} of the time, not {expected}"
+            );
+        }
+    }
+
+    /// A line reader like [`scripted_reader`] that also records the prompts
+    /// it was given.
+    fn recording_reader(lines: &[&str]) -> (LineReader, Rc<RefCell<Vec<String>>>) {
+        let prompts = Rc::new(RefCell::new(Vec::new()));
+        let scripted = scripted_reader(lines);
+        let reader = LineReader::new({
+            let prompts = prompts.clone();
+            move |prompt| {
+                prompts.borrow_mut().push(prompt.to_owned());
+                scripted.read_line(prompt)
+            }
+        });
+        (reader, prompts)
+    }
+
+    #[test]
+    fn human_prompts_use_the_messages() {
+        use termcolor::{ColorChoice, StandardStream};
+
+        use crate::{messages::Messages, Game, GameSettings, State};
+
+        let messages = Messages {
+            turn_prompt: " joue : ".into(),
+            draw_prompt: ", nulle ? (`Yes` ou `No`) ".into(),
+            ..Default::default()
+        };
+        let (black_reader, black_prompts) = recording_reader(&["draw"]);
+        let (white_reader, white_prompts) = recording_reader(&["no"]);
+        let mut game = Game::new(
+            Box::new(HumanPlayer::new(None).with_line_reader(white_reader)),
+            Box::new(HumanPlayer::new("Thibault".to_owned()).with_line_reader(black_reader)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                game_record: false,
+                saves_game_dir: None,
+                messages,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // the draw is declined, the input of Black is over when Black is
+        // asked to move again
+        game.play().unwrap();
+        assert_eq!(game.state(), &State::Aborted);
+        assert_eq!(
+            *black_prompts.borrow(),
+            ["Black (Thibault) joue : ", "Black (Thibault) joue : "]
+        );
+        assert_eq!(*white_prompts.borrow(), [", nulle ? (`Yes` ou `No`) "]);
+    }
+
+    #[test]
+    fn help_asks_the_move_again() {
+        use termcolor::{ColorChoice, StandardStream};
+
+        use crate::{Game, GameSettings, State};
+
+        let (black_reader, black_prompts) = recording_reader(&["?", "help"]);
+        let mut game = Game::new(
+            Box::new(RandomPlayer::new(None)),
+            Box::new(HumanPlayer::new(None).with_line_reader(black_reader)),
+            StandardStream::stdout(ColorChoice::Never),
+            GameSettings {
+                render: false,
+                game_record: false,
+                saves_game_dir: None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // the help is printed twice then the input is over, no move was
+        // played
+        game.play().unwrap();
+        assert_eq!(game.state(), &State::Aborted);
+        assert!(game.history().is_empty());
+        assert_eq!(*black_prompts.borrow(), ["Black's turn: "; 3]);
+    }
+}
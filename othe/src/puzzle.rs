@@ -0,0 +1,92 @@
+//! Training puzzles, a position where the player must find the best move.
+//!
+//! A puzzle is stored as json, the board is written in the notation of
+//! [`Board::from_str`], e.g:
+//!
+//! ```json
+//! {
+//!   "board": "---------------------------OX------XO---------------------------",
+//!   "side": "B",
+//!   "solution": [{ "col": 5, "row": 4 }]
+//! }
+//! ```
+
+use std::{io::Read, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Board, Disc, Move, OthelloError, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Puzzle {
+    /// The position to solve
+    #[serde(serialize_with = "serialize_board")]
+    #[serde(deserialize_with = "deserialize_board")]
+    pub board: Board,
+    /// The player to move, cannot be `Disc::Empty`
+    pub side: Disc,
+    /// The best moves, finding any of them solves the puzzle
+    pub solution: Vec<Move>,
+}
+
+impl Puzzle {
+    /// Deserializes a puzzle from the json read from the `reader`.
+    pub fn from_reader(reader: impl Read) -> Result<Puzzle> {
+        let puzzle: Puzzle = serde_json::from_reader(reader)?;
+        if puzzle.side == Disc::Empty {
+            return Err(OthelloError::InvalidPlayerColor(puzzle.side.to_string()));
+        }
+        Ok(puzzle)
+    }
+
+    /// Return `true` if `mov` is legal and one of the moves of the solution.
+    pub fn check(&self, mov: Move) -> bool {
+        self.board.is_legal(self.side, mov) && self.solution.contains(&mov)
+    }
+}
+
+fn serialize_board<S: Serializer>(board: &Board, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&board.to_notation())
+}
+
+fn deserialize_board<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+    let notation = String::deserialize(deserializer)?;
+    Board::from_str(&notation).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_side_cannot_be_empty() {
+        let json = r#"{
+            "board": "---------------------------OX------XO---------------------------",
+            "side": "-",
+            "solution": [{ "col": 5, "row": 4 }]
+        }"#;
+        assert!(matches!(
+            Puzzle::from_reader(json.as_bytes()),
+            Err(OthelloError::InvalidPlayerColor(_))
+        ));
+
+        let puzzle = Puzzle::from_reader(json.replace(r#""-""#, r#""B""#).as_bytes()).unwrap();
+        assert!(puzzle.check(Move { col: 5, row: 4 }));
+    }
+
+    #[test]
+    fn wrong_moves_fail_the_puzzle() {
+        let mut puzzle = Puzzle {
+            board: Board::new(),
+            side: Disc::Black,
+            solution: vec![Move::from_algebric("f5").unwrap()],
+        };
+        // legal but not in the solution
+        assert!(!puzzle.check(Move::from_algebric("d3").unwrap()));
+        // not legal, even if the solution lists it
+        let a1 = Move::from_algebric("a1").unwrap();
+        puzzle.solution.push(a1);
+        assert!(!puzzle.check(a1));
+        assert!(puzzle.check(Move::from_algebric("f5").unwrap()));
+    }
+}
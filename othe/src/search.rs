@@ -0,0 +1,236 @@
+//! Search of the best move, an alpha-beta search with a heuristic evaluation
+//! for the opening and the midgame and an exact solver for the endgame.
+//!
+//! The search works on the bitboards of the player to move and of his
+//! opponent, see [`Board::to_bitboards`].
+
+use std::fmt::{self, Display};
+
+use crate::{bitboard, bits, Board, Disc, Move};
+
+/// Value of a won position in the heuristic search, it is above any
+/// heuristic evaluation so the search prefers a sure win.
+const WIN: i32 = 10_000;
+
+/// How deep the search looks ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchDepth {
+    /// Search this number of plies and evaluate the positions with
+    /// [`evaluate`].
+    Plies(u8),
+    /// Search until the end of the game and maximize the final disc
+    /// difference.
+    Exact,
+}
+
+/// The evaluation of a move by [`rank_moves`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    /// The heuristic evaluation of the position reached by the search, see
+    /// [`evaluate`].
+    Heuristic(i16),
+    /// The game ends within the search, this is the final disc difference of
+    /// the player, positive if he wins.
+    Final(i8),
+}
+
+impl Score {
+    /// Convert the value returned by the search at `depth`.
+    fn from_value(value: i32, depth: SearchDepth) -> Score {
+        match depth {
+            SearchDepth::Exact => Score::Final(value as i8),
+            // the won positions are offset by `WIN`, above any heuristic
+            // evaluation
+            SearchDepth::Plies(_) if value > WIN / 2 => Score::Final((value - WIN) as i8),
+            SearchDepth::Plies(_) if value < -WIN / 2 => Score::Final((value + WIN) as i8),
+            SearchDepth::Plies(_) => Score::Heuristic(value as i16),
+        }
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Heuristic(value) => f.pad(&format!("{value:+}")),
+            Score::Final(0) => f.pad("draw"),
+            Score::Final(diff) if *diff > 0 => f.pad(&format!("wins by {diff}")),
+            Score::Final(diff) => f.pad(&format!("loses by {}", -diff)),
+        }
+    }
+}
+
+/// Simple evaluation of the board for `player`, it values the corners, the
+/// mobility and penalizes the frontier discs. Higher is better.
+pub fn evaluate(board: &Board, player: Disc) -> i16 {
+    let player_bb = board.bitboard(player);
+    let opp_bb = board.bitboard(!player);
+    evaluate_bitboards(player_bb, opp_bb) as i16
+}
+
+fn evaluate_bitboards(player_bb: u64, opp_bb: u64) -> i32 {
+    let corners = (player_bb & Board::CORNERS).count_ones() as i32
+        - (opp_bb & Board::CORNERS).count_ones() as i32;
+    let mobility = bitboard::moves(player_bb, opp_bb).count_ones() as i32
+        - bitboard::moves(opp_bb, player_bb).count_ones() as i32;
+
+    // the discs adjacent to an empty square
+    let empty = !(player_bb | opp_bb);
+    let frontier_squares = bitboard::neighbours(empty);
+    let frontier = (player_bb & frontier_squares).count_ones() as i32
+        - (opp_bb & frontier_squares).count_ones() as i32;
+
+    10 * corners + 2 * mobility - frontier
+}
+
+/// Return the best move of `player` found by searching at `depth`, or `None`
+/// if he can't move.
+pub fn best_move(board: &Board, player: Disc, depth: SearchDepth) -> Option<Move> {
+    // the first of the best moves in the order of the squares, like the
+    // ranking
+    search_root(board, player, depth, true)
+        .into_iter()
+        .fold(None, |best, (mov, value)| match best {
+            Some((_, best_value)) if best_value >= value => best,
+            _ => Some((mov, value)),
+        })
+        .map(|(mov, _)| mov)
+}
+
+/// Return the legal moves of `player` with their evaluation found by searching
+/// at `depth`, the best move first. The evaluations are from the point of
+/// view of `player`, higher is better.
+pub fn rank_moves(board: &Board, player: Disc, depth: SearchDepth) -> Vec<(Move, Score)> {
+    let mut ranked = search_root(board, player, depth, false);
+    // the sort is stable, the equal moves stay in the order of the squares
+    ranked.sort_by_key(|&(_, value)| -value);
+
+    ranked
+        .into_iter()
+        .map(|(mov, value)| (mov, Score::from_value(value, depth)))
+        .collect()
+}
+
+/// Search the legal moves of `player` at `depth` and return their values, in
+/// the order of the squares. If `narrow`, the search of each move is cut as
+/// soon as it can't beat the previous ones: only the first of the best moves
+/// has its exact value, the others are upper bounds.
+fn search_root(board: &Board, player: Disc, depth: SearchDepth, narrow: bool) -> Vec<(Move, i32)> {
+    let player_bb = board.bitboard(player);
+    let opp_bb = board.bitboard(!player);
+
+    let mut alpha = -i32::MAX;
+    bits(bitboard::moves(player_bb, opp_bb))
+        .map(|sq| {
+            let (new_player, new_opp) = play(player_bb, opp_bb, sq);
+            let beta = if narrow { -alpha } else { i32::MAX };
+            let value = match depth {
+                SearchDepth::Plies(plies) => -alpha_beta(
+                    new_opp,
+                    new_player,
+                    plies.saturating_sub(1),
+                    -i32::MAX,
+                    beta,
+                    false,
+                ),
+                SearchDepth::Exact => -solve(new_opp, new_player, -i32::MAX, beta, false),
+            };
+            alpha = alpha.max(value);
+            (Move::from_idx(sq), value)
+        })
+        .collect()
+}
+
+/// Play the move `sq` and return the new bitboards of the player and his
+/// opponent.
+#[inline]
+fn play(player_bb: u64, opp_bb: u64, sq: u8) -> (u64, u64) {
+    let flipped = bitboard::flips(player_bb, opp_bb, sq);
+    (player_bb | flipped | 1 << sq, opp_bb & !flipped)
+}
+
+/// Negamax search with alpha-beta pruning, return the evaluation of the
+/// position for the player to move. `passed` is true if the opponent couldn't
+/// move at the previous ply.
+fn alpha_beta(
+    player_bb: u64,
+    opp_bb: u64,
+    depth: u8,
+    mut alpha: i32,
+    beta: i32,
+    passed: bool,
+) -> i32 {
+    let moves = bitboard::moves(player_bb, opp_bb);
+    if moves == 0 {
+        if passed {
+            // no one can move, the game is over
+            let diff = player_bb.count_ones() as i32 - opp_bb.count_ones() as i32;
+            return diff.signum() * WIN + diff;
+        }
+        return -alpha_beta(opp_bb, player_bb, depth, -beta, -alpha, true);
+    }
+    if depth == 0 {
+        return evaluate_bitboards(player_bb, opp_bb);
+    }
+
+    for sq in bits(moves) {
+        let (new_player, new_opp) = play(player_bb, opp_bb, sq);
+        let value = -alpha_beta(new_opp, new_player, depth - 1, -beta, -alpha, false);
+        if value > alpha {
+            alpha = value;
+            if alpha >= beta {
+                break;
+            }
+        }
+    }
+
+    alpha
+}
+
+/// Exact search of the end of the game, return the final disc difference for
+/// the player to move. `passed` is true if the opponent couldn't move at the
+/// previous ply.
+fn solve(player_bb: u64, opp_bb: u64, mut alpha: i32, beta: i32, passed: bool) -> i32 {
+    let moves = bitboard::moves(player_bb, opp_bb);
+    if moves == 0 {
+        if passed {
+            return player_bb.count_ones() as i32 - opp_bb.count_ones() as i32;
+        }
+        return -solve(opp_bb, player_bb, -beta, -alpha, true);
+    }
+
+    for sq in bits(moves) {
+        let (new_player, new_opp) = play(player_bb, opp_bb, sq);
+        let value = -solve(new_opp, new_player, -beta, -alpha, false);
+        if value > alpha {
+            alpha = value;
+            if alpha >= beta {
+                break;
+            }
+        }
+    }
+
+    alpha
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn corner_is_ranked_first() {
+        // Black can take the corner a1, then White can't move and Black
+        // takes the last White disc with c8, winning by 7. The other move c8
+        // lets White play e1.
+        let board = Board::from_str(&format!("-OOX----{}XO------", "-".repeat(48))).unwrap();
+        let a1 = Move::from_algebric("a1").unwrap();
+
+        for depth in [SearchDepth::Plies(4), SearchDepth::Exact] {
+            let ranked = rank_moves(&board, Disc::Black, depth);
+            assert_eq!(ranked.len(), 2);
+            assert_eq!(ranked[0], (a1, Score::Final(7)));
+            assert_eq!(best_move(&board, Disc::Black, depth), Some(a1));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Statistics of the players kept across the games, stored in a JSON file next
+//! to the game saves.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Disc, GameSave, Result, State};
+
+/// Results of the games of a player, see [`PlayerStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerRecord {
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl PlayerRecord {
+    /// Ratio of the games won, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// The results of the finished games of every player, keyed by the name of
+/// the player.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayerStats {
+    records: BTreeMap<String, PlayerRecord>,
+}
+
+impl PlayerStats {
+    /// Name of the file of the statistics in the saves directory.
+    pub const FILE_NAME: &'static str = "players.json";
+
+    /// Read the statistics from the file at `path`, they are empty if the
+    /// file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<PlayerStats> {
+        match File::open(path) {
+            Ok(file) => Ok(serde_json::from_reader(BufReader::new(file))?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(PlayerStats::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the statistics to the file at `path`, its directory is created
+    /// if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Record the result of the game of the `save` for both players. The
+    /// unfinished games and the players without a name are skipped.
+    pub fn update(&mut self, save: &GameSave) {
+        let winner = match save.end_state {
+            State::Winned { winner_color, .. } => Some(winner_color),
+            State::TimedOut { loser } => Some(!loser),
+            State::Draw => None,
+            _ => return,
+        };
+
+        for (color, name) in [
+            (Disc::Black, &save.black_name),
+            (Disc::White, &save.white_name),
+        ] {
+            let Some(name) = name else {
+                continue;
+            };
+
+            let record = self.records.entry(name.to_string()).or_default();
+            record.games += 1;
+            match winner {
+                Some(winner) if winner == color => record.wins += 1,
+                Some(_) => record.losses += 1,
+                None => record.draws += 1,
+            }
+        }
+    }
+
+    /// Return the record of the player named `name`.
+    pub fn get(&self, name: &str) -> Option<&PlayerRecord> {
+        self.records.get(name)
+    }
+
+    /// Return the players sorted by their number of wins and then by their
+    /// win rate, the best first.
+    pub fn leaderboard(&self) -> Vec<(&str, &PlayerRecord)> {
+        let mut players = self
+            .records
+            .iter()
+            .map(|(name, record)| (name.as_str(), record))
+            .collect::<Vec<_>>();
+        players.sort_by(|(_, a), (_, b)| {
+            b.wins
+                .cmp(&a.wins)
+                .then(b.win_rate().total_cmp(&a.win_rate()))
+        });
+        players
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::player::HumanPlayer;
+
+    #[test]
+    fn finished_games_update_the_tallies() {
+        let path = env::temp_dir()
+            .join(format!("othe-stats-{}", std::process::id()))
+            .join(PlayerStats::FILE_NAME);
+        let mut stats = PlayerStats::load(&path).unwrap();
+        assert_eq!(stats, PlayerStats::default());
+
+        let ada = HumanPlayer::new("Ada".to_owned());
+        let bob = HumanPlayer::new("Bob".to_owned());
+        let game = |end_state| GameSave {
+            end_state,
+            ..GameSave::new("game".to_owned(), &ada, &bob)
+        };
+        stats.update(&game(State::Winned {
+            winner_color: Disc::Black,
+            winner_name: "Ada".to_owned(),
+            winner_score: 40,
+            loser_score: 24,
+            empty_squares: 0,
+        }));
+        stats.update(&game(State::Draw));
+        // the aborted games aren't counted
+        stats.update(&game(State::Aborted));
+
+        let record = |games, wins, losses, draws| PlayerRecord {
+            games,
+            wins,
+            losses,
+            draws,
+        };
+        assert_eq!(stats.get("Ada"), Some(&record(2, 1, 0, 1)));
+        assert_eq!(stats.get("Bob"), Some(&record(2, 0, 1, 1)));
+        assert_eq!(
+            stats
+                .leaderboard()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["Ada", "Bob"]
+        );
+
+        // the file is created by the first save
+        stats.save(&path).unwrap();
+        assert_eq!(PlayerStats::load(&path).unwrap(), stats);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -1,5 +1,8 @@
+use std::{borrow::Cow, env, ffi::OsStr};
+
 use lazy_static::lazy_static;
-use termcolor::{Color, ColorSpec};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream};
+use unicode_width::UnicodeWidthChar;
 
 lazy_static! {
     pub static ref BOARD_EDGES: ColorSpec = ColorSpec::new()
@@ -24,8 +27,165 @@ lazy_static! {
         .set_fg(Some(Color::Ansi256(15)))
         .set_bold(true)
         .clone();
+    pub static ref SUBDUED: ColorSpec = ColorSpec::new().set_fg(Some(Color::Ansi256(244))).clone();
     pub static ref ERROR: ColorSpec = ColorSpec::new()
         .set_fg(Some(Color::Red))
         .set_bold(true)
         .clone();
 }
+
+/// Return the standard output, colored according to [`color_choice`].
+pub fn stdout() -> StandardStream {
+    StandardStream::stdout(color_choice())
+}
+
+/// Return when to color the output, according to the `NO_COLOR` and
+/// `CLICOLOR_FORCE` environment variables, see [`color_choice_from`].
+pub fn color_choice() -> ColorChoice {
+    color_choice_from(
+        env::var_os("NO_COLOR").as_deref(),
+        env::var_os("CLICOLOR_FORCE").as_deref(),
+    )
+}
+
+/// Return when to color the output from the values of the `NO_COLOR` and
+/// `CLICOLOR_FORCE` environment variables. A non-empty `NO_COLOR` disables
+/// the colors, then a `CLICOLOR_FORCE` other than `0` forces them, otherwise
+/// the colors are used if the terminal supports them.
+pub fn color_choice_from(no_color: Option<&OsStr>, clicolor_force: Option<&OsStr>) -> ColorChoice {
+    if no_color.is_some_and(|v| !v.is_empty()) {
+        return ColorChoice::Never;
+    }
+    if clicolor_force.is_some_and(|v| !v.is_empty() && v != "0") {
+        return ColorChoice::Always;
+    }
+    ColorChoice::Auto
+}
+
+/// The characters used to render the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderStyle {
+    /// The legal moves are rendered with a bullet, `•`.
+    #[default]
+    Unicode,
+    /// Only ASCII characters, the legal moves are rendered with a dot, `.`.
+    Ascii,
+}
+
+impl RenderStyle {
+    /// Return the character drawn on the squares of the legal moves.
+    pub fn legal_move_marker(self) -> char {
+        match self {
+            RenderStyle::Unicode => '•',
+            RenderStyle::Ascii => '.',
+        }
+    }
+}
+
+/// The characters of the edges of the board in [`Board::to_ascii_art`].
+///
+/// [`Board::to_ascii_art`]: crate::Board::to_ascii_art
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    /// Only ASCII characters, e.g: `+---+`.
+    #[default]
+    Ascii,
+    /// Box-drawing characters, e.g: `┌───┬───┐`.
+    Unicode,
+}
+
+impl BorderStyle {
+    /// Return the top, the middle and the bottom horizontal edges of the
+    /// board.
+    pub fn edges(self) -> [&'static str; 3] {
+        match self {
+            BorderStyle::Ascii => ["+---+---+---+---+---+---+---+---+"; 3],
+            BorderStyle::Unicode => [
+                "┌───┬───┬───┬───┬───┬───┬───┬───┐",
+                "├───┼───┼───┼───┼───┼───┼───┼───┤",
+                "└───┴───┴───┴───┴───┴───┴───┴───┘",
+            ],
+        }
+    }
+
+    /// Return the vertical edge between two squares.
+    pub fn vertical(self) -> char {
+        match self {
+            BorderStyle::Ascii => '|',
+            BorderStyle::Unicode => '│',
+        }
+    }
+}
+
+/// Is the character visible and as wide as one column of the terminal? The
+/// control characters, the whitespaces, the combining marks and the wide
+/// characters (CJK, emojis..) are not.
+pub fn is_single_width(ch: char) -> bool {
+    !ch.is_whitespace() && char_width(ch) == 1
+}
+
+/// Return the number of columns of the terminal taken by the character, 0 for
+/// the control characters and the combining marks, 2 for the wide characters
+/// (CJK, emojis..) and 1 for the others.
+pub fn char_width(ch: char) -> usize {
+    UnicodeWidthChar::width(ch).unwrap_or(0)
+}
+
+/// Return the number of columns of the terminal taken by the string.
+pub fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Truncate the string so it is at most `max_width` columns wide, the
+/// truncated strings end with an ellipsis.
+pub fn truncate_to_width(s: &str, max_width: usize) -> Cow<'_, str> {
+    if str_width(s) <= max_width {
+        return Cow::Borrowed(s);
+    }
+
+    let mut width = 0;
+    let mut truncated = String::new();
+    for ch in s.chars() {
+        // keep one column for the ellipsis
+        width += char_width(ch);
+        if width + 1 > max_width {
+            break;
+        }
+        truncated.push(ch);
+    }
+    truncated.push('…');
+    Cow::Owned(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_names_are_measured_in_columns() {
+        assert_eq!(str_width("Othello"), 7);
+        assert_eq!(str_width("オセロ"), 6);
+        // rocket, sun and ballet shoes emojis
+        assert_eq!(str_width("\u{1F680}\u{2600}\u{FE0F}\u{1FA70}"), 5);
+        assert_eq!(str_width("e\u{301}"), 1);
+
+        let truncated = truncate_to_width("オセロの名人", 7);
+        assert_eq!(truncated, "オセロ…");
+        assert!(str_width(&truncated) <= 7);
+        assert_eq!(truncate_to_width("Bot", 7), "Bot");
+    }
+
+    #[test]
+    fn colors_follow_the_environment() {
+        let var = |value| Some(OsStr::new(value));
+        assert_eq!(color_choice_from(None, None), ColorChoice::Auto);
+        assert_eq!(color_choice_from(var("1"), None), ColorChoice::Never);
+        // an empty `NO_COLOR` is ignored
+        assert_eq!(color_choice_from(var(""), None), ColorChoice::Auto);
+        assert_eq!(color_choice_from(None, var("1")), ColorChoice::Always);
+        assert_eq!(color_choice_from(None, var("0")), ColorChoice::Auto);
+        assert_eq!(color_choice_from(None, var("")), ColorChoice::Auto);
+        // `NO_COLOR` wins
+        assert_eq!(color_choice_from(var("1"), var("1")), ColorChoice::Never);
+    }
+}
@@ -0,0 +1,245 @@
+//! Interoperability with the [WTHOR][wthor] database format of the French
+//! Othello Federation.
+//!
+//! A `.wtb` file is a 16 bytes header followed by game records of 68 bytes,
+//! only the game records are supported here. The layout of a record is:
+//!
+//! | Offset | Size | Content                                                  |
+//! |--------|------|----------------------------------------------------------|
+//! | 0      | 2    | Tournament number, little endian                         |
+//! | 2      | 2    | Black player number, little endian                       |
+//! | 4      | 2    | White player number, little endian                       |
+//! | 6      | 1    | Black's score at the end of the game                     |
+//! | 7      | 1    | Theoretical score of Black                               |
+//! | 8      | 60   | The moves, `10 * row + col` (`a1` is `11`, `h8` is `88`) |
+//!
+//! The tournament and player numbers index other files of the database, 0the
+//! doesn't know them so they are set to zero. Black's score uses the
+//! championship style scores of [`State`], the empty squares belong to the
+//! winner, unless the game was played with [`ScoringRule::DiscCountOnly`]. The
+//! theoretical score requires a perfect solver, it is set to the real score.
+//! Passes are not recorded and the unused moves are zeros. The games start from
+//! the standard position with Black, the other games can't be exported.
+//!
+//! [wthor]: https://www.ffothello.org/informatique/la-base-wthor/
+//! [`ScoringRule::DiscCountOnly`]: crate::ScoringRule::DiscCountOnly
+
+use crate::{Board, Disc, GameSave, Move, OthelloError, Result, State};
+
+/// Size in bytes of a WTHOR game record.
+pub const RECORD_SIZE: usize = 68;
+
+/// Offset of the black's score in a WTHOR game record.
+const SCORE_OFFSET: usize = 6;
+
+/// Offset of the theoretical black's score in a WTHOR game record.
+const THEORETICAL_SCORE_OFFSET: usize = 7;
+
+/// Offset of the moves in a WTHOR game record.
+const MOVES_OFFSET: usize = 8;
+
+impl GameSave {
+    /// Convert the save into a WTHOR game record of [`RECORD_SIZE`] bytes,
+    /// see the [module documentation](crate::wthor) for the layout.
+    ///
+    /// It fails with [`OthelloError::InvalidWthorRecord`] if the game isn't
+    /// over or was lost on time, if it has more than 60 moves or if it doesn't
+    /// start from the standard position with Black.
+    pub fn to_wthor_record(&self) -> Result<Vec<u8>> {
+        if self.start_board() != Board::new() || self.first_player != Disc::Black {
+            return Err(OthelloError::InvalidWthorRecord(
+                "the game must start from the standard position with Black",
+            ));
+        }
+        if self.moves.len() > 60 {
+            return Err(OthelloError::InvalidWthorRecord(
+                "an othello game has at most 60 moves",
+            ));
+        }
+
+        let black_score = match &self.end_state {
+            State::Winned {
+                winner_color: Disc::Black,
+                winner_score,
+                ..
+            } => *winner_score,
+            State::Winned { loser_score, .. } => *loser_score,
+            // a draw may be agreed before the end or be counted without the
+            // empty squares, the record has the discs of the finished board.
+            State::Draw => {
+                let mut board = self.start_board();
+                board.play_with_passes(self.first_player, &self.moves_with_passes())?;
+                if !board.is_terminal() {
+                    return Err(OthelloError::InvalidWthorRecord("the game is not over"));
+                }
+                let (_, black, _) = board.scores();
+                black
+            }
+            State::Playing | State::TurnForfeited | State::Aborted => {
+                return Err(OthelloError::InvalidWthorRecord("the game is not over"));
+            }
+            // the records only store the discs at the end of the game
+            State::TimedOut { .. } => {
+                return Err(OthelloError::InvalidWthorRecord(
+                    "a game lost on time has no final score",
+                ));
+            }
+        };
+
+        let mut record = vec![0; RECORD_SIZE];
+        record[SCORE_OFFSET] = black_score;
+        record[THEORETICAL_SCORE_OFFSET] = black_score;
+
+        for (byte, mov) in record[MOVES_OFFSET..].iter_mut().zip(&self.moves) {
+            *byte = 10 * (mov.row + 1) + mov.col + 1;
+        }
+
+        Ok(record)
+    }
+
+    /// Parse a WTHOR game record, the moves are replayed to check they are
+    /// legal and to compute the end state of the game. The players are humans
+    /// without names because the record only contains their number in the
+    /// database.
+    pub fn from_wthor_record(record: &[u8], title: String) -> Result<GameSave> {
+        if record.len() != RECORD_SIZE {
+            return Err(OthelloError::InvalidWthorRecord(
+                "a record must be 68 bytes long",
+            ));
+        }
+
+        let mut moves = Vec::new();
+        for &byte in record[MOVES_OFFSET..].iter().take_while(|&&byte| byte != 0) {
+            let (row, col) = (byte / 10, byte % 10);
+            if !(1..=8).contains(&row) || !(1..=8).contains(&col) {
+                return Err(OthelloError::InvalidWthorRecord("invalid move"));
+            }
+            moves.push(Move {
+                col: col - 1,
+                row: row - 1,
+            });
+        }
+
+        // the records don't store the passes, they are found when replaying
+        let save = GameSave::from_moves(title, moves, None, None)?;
+        if save.end_state == State::Aborted {
+            return Err(OthelloError::InvalidWthorRecord("the game is not over"));
+        }
+
+        Ok(save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+
+    use super::*;
+    use crate::bits;
+
+    #[test]
+    fn full_game_round_trip() {
+        // play the first legal move until the end of the game
+        let mut board = crate::Board::new();
+        let mut turn = Disc::Black;
+        let mut moves = Vec::new();
+        loop {
+            match board.terminal_or_pass(turn) {
+                crate::TurnOutcome::Continue { legal_moves } => {
+                    let mov = Move::from_idx(legal_moves.trailing_zeros() as u8);
+                    board.apply_move(turn, mov);
+                    moves.push(mov);
+                }
+                crate::TurnOutcome::Pass { .. } => {}
+                crate::TurnOutcome::GameOver(_) => break,
+            }
+            turn = !turn;
+        }
+
+        let save = GameSave::from_moves(String::from("Full game"), moves, None, None).unwrap();
+        let record = save.to_wthor_record().unwrap();
+        assert_eq!(record.len(), RECORD_SIZE);
+
+        let parsed = GameSave::from_wthor_record(&record, String::from("Full game")).unwrap();
+        assert_eq!(parsed.moves, save.moves);
+        assert_eq!(parsed.end_state, save.end_state);
+    }
+
+    #[test]
+    fn unfinished_game_is_not_exported() {
+        let save = GameSave::from_transcript(String::from("Unfinished"), "f5d6c3").unwrap();
+        assert!(matches!(
+            save.to_wthor_record(),
+            Err(OthelloError::InvalidWthorRecord(_))
+        ));
+    }
+
+    #[test]
+    fn timed_out_game_is_not_exported() {
+        let mut save = GameSave::from_transcript(String::from("Timeout"), "f5d6").unwrap();
+        save.end_state = State::TimedOut { loser: Disc::Black };
+        assert!(matches!(
+            save.to_wthor_record(),
+            Err(OthelloError::InvalidWthorRecord(_))
+        ));
+    }
+
+    #[test]
+    fn non_standard_start_is_not_exported() {
+        // Black flips all the white discs
+        let mut save =
+            GameSave::from_transcript(String::from("Wipeout"), "d3c3b3d2e1d6d7e3f4").unwrap();
+        assert!(save.to_wthor_record().is_ok());
+
+        save.start = Some(Board::with_start(crate::StartKind::Parallel));
+        assert!(matches!(
+            save.to_wthor_record(),
+            Err(OthelloError::InvalidWthorRecord(_))
+        ));
+
+        save.start = None;
+        save.first_player = Disc::White;
+        assert!(matches!(
+            save.to_wthor_record(),
+            Err(OthelloError::InvalidWthorRecord(_))
+        ));
+    }
+
+    #[test]
+    fn draws_export_the_discs_of_the_finished_board() {
+        // the players agreed to a draw before the end
+        let mut save = GameSave::from_transcript(String::from("Agreed"), "f5d6").unwrap();
+        save.end_state = State::Draw;
+        assert!(matches!(
+            save.to_wthor_record(),
+            Err(OthelloError::InvalidWthorRecord(_))
+        ));
+
+        // play random games until one ends in a draw
+        let mut rng = StdRng::seed_from_u64(862);
+        let save = loop {
+            let mut board = Board::new();
+            let mut turn = Disc::Black;
+            let mut moves = Vec::new();
+            while !board.is_terminal() {
+                if let Some(idx) = bits(board.legal_moves(turn)).choose(&mut rng) {
+                    let mov = Move::from_idx(idx);
+                    board.apply_move(turn, mov);
+                    moves.push(mov);
+                }
+                turn = !turn;
+            }
+
+            let save = GameSave::from_moves(String::from("Draw"), moves, None, None).unwrap();
+            if save.end_state == State::Draw {
+                break save;
+            }
+        };
+
+        let record = save.to_wthor_record().unwrap();
+        let black = save.positions().last().unwrap().board.scores().1;
+        assert_eq!(record[SCORE_OFFSET], black);
+        let parsed = GameSave::from_wthor_record(&record, String::from("Draw")).unwrap();
+        assert_eq!(parsed.end_state, State::Draw);
+    }
+}
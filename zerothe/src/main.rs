@@ -1,67 +1,237 @@
 use std::{
     error::Error,
     fs::{self, File},
-    io::{self, Read, Write},
+    io::{self, BufRead, BufReader, IsTerminal, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
     str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
 };
 
 use othe::{
-    player::{HumanPlayer, Player, RandomPlayer},
-    style, Board, Disc, Game, GameSave, GameSettings, OthelloError, State, LICENSE, OTHELLO_RULES,
-    VERSION_AND_GIT_HASH,
+    opening::{opening_name, opening_stats},
+    player::{
+        AdaptivePlayer, BalancedPlayer, DepthSchedule, HumanPlayer, LineReader, Player,
+        RandomPlayer,
+    },
+    prompt,
+    puzzle::Puzzle,
+    read_input,
+    search::{self, SearchDepth},
+    stats::PlayerStats,
+    style::{self, RenderStyle},
+    Board, Disc, Game, GameSave, GameSettings, InterruptFlag, LegalMoveHints, Move, OthelloError,
+    Outcome, SaveFormat, ScoringRule, StartKind, LICENSE, OTHELLO_RULES, VERSION_AND_GIT_HASH,
 };
-use termcolor::{ColorChoice, StandardStream, WriteColor};
-
-fn player_init(s: &mut StandardStream, color: Disc) -> Result<Box<dyn Player>, OthelloError> {
-    let mut buf = String::new();
-    write!(s, "{color} player's type (1): ")?;
-    s.flush()?;
-    io::stdin().read_line(&mut buf)?;
-    // pop the newline character
-    buf.pop();
+use rustyline::{error::ReadlineError, DefaultEditor};
+use termcolor::{StandardStream, WriteColor};
+
+/// Read a line of the standard input, fails at the end of the input.
+fn input() -> Result<String, OthelloError> {
+    read_input(&mut io::stdin().lock())?.ok_or(OthelloError::EndOfInput)
+}
+
+/// Is the "Abort on Ctrl-C" setting enabled? The handler can't be removed
+/// once installed, it quits 0the right away like the default behaviour of
+/// Ctrl-C while the setting is disabled.
+static ABORT_ON_CTRL_C: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the "Abort on Ctrl-C" setting, return the flag set by
+/// Ctrl-C to abort the games if it is enabled. The handler is installed the
+/// first time it is enabled. A second Ctrl-C before the flag is reset quits
+/// 0the, e.g: while waiting for a command.
+fn abort_on_ctrl_c(enabled: bool) -> Result<Option<InterruptFlag>, OthelloError> {
+    ABORT_ON_CTRL_C.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        return Ok(None);
+    }
+    interrupt_flag().map(Some)
+}
+
+/// Return the flag set by Ctrl-C, see [`abort_on_ctrl_c`].
+fn interrupt_flag() -> Result<InterruptFlag, OthelloError> {
+    static FLAG: OnceLock<InterruptFlag> = OnceLock::new();
+    if let Some(flag) = FLAG.get() {
+        return Ok(flag.clone());
+    }
+
+    let flag = InterruptFlag::new();
+    let handler_flag = flag.clone();
+    ctrlc::set_handler(move || {
+        if !ABORT_ON_CTRL_C.load(Ordering::SeqCst) || handler_flag.is_interrupted() {
+            process::exit(130);
+        }
+        handler_flag.interrupt();
+    })
+    .map_err(|e| OthelloError::IoError(io::Error::other(e)))?;
+    Ok(FLAG.get_or_init(|| flag).clone())
+}
+
+/// Return the reader of the commands and of the moves of the human players.
+/// It has a history and line editing when the standard input is a terminal,
+/// otherwise it reads the raw lines.
+fn line_reader() -> LineReader {
+    let mut editor = if io::stdin().is_terminal() {
+        DefaultEditor::new().ok()
+    } else {
+        None
+    };
+
+    LineReader::new(move |msg| {
+        let Some(editor) = &mut editor else {
+            return read_raw_line(&mut io::stdout(), &mut io::stdin().lock(), msg);
+        };
+
+        match editor.readline(msg) {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    // the history is only a convenience
+                    let _ = editor.add_history_entry(line.as_str());
+                }
+                Ok(Some(line))
+            }
+            // like the end of the input, e.g: Ctrl-D or Ctrl-C
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => Ok(None),
+            Err(ReadlineError::Io(e)) => Err(e),
+            Err(e) => Err(io::Error::other(e)),
+        }
+    })
+}
+
+/// Read a line of the `input` after writing the prompt `msg` to `output`,
+/// without line editing. It is the fallback of [`line_reader`].
+fn read_raw_line(
+    output: &mut impl Write,
+    input: &mut impl BufRead,
+    msg: &str,
+) -> io::Result<Option<String>> {
+    prompt(output, msg)?;
+    read_input(input)
+}
+
+/// The depths of the search of the balanced bot, in plies.
+const BALANCED_MIN_PLIES: u8 = 1;
+const BALANCED_MAX_PLIES: u8 = 5;
+
+fn player_init(
+    s: &mut StandardStream,
+    color: Disc,
+    line_reader: &LineReader,
+) -> Result<Box<dyn Player>, OthelloError> {
+    prompt(s, format_args!("{color} player's type (1): "))?;
+    let mut buf = input()?;
     match buf.as_str() {
         "" | "1" => {
             // human player
-            buf.clear();
-            write!(s, "                   name: ")?;
-            s.flush()?;
-            io::stdin().read_line(&mut buf)?;
-            buf.pop();
-            Ok(Box::new(HumanPlayer::new(buf)))
+            prompt(s, "                   name: ")?;
+            buf = input()?;
+            Ok(Box::new(
+                HumanPlayer::new(buf).with_line_reader(line_reader.clone()),
+            ))
         }
         "2" => {
             // random bot player
-            Ok(Box::new(RandomPlayer::default()))
+            prompt(s, "                   name: ")?;
+            buf = input()?;
+            Ok(Box::new(RandomPlayer::new(buf)))
+        }
+        "3" => {
+            // adaptive bot player
+            prompt(s, "                   name: ")?;
+            buf = input()?;
+            Ok(Box::new(AdaptivePlayer::new(buf, DepthSchedule::default())))
+        }
+        "4" => {
+            // balanced bot player
+            prompt(s, "                   name: ")?;
+            buf = input()?;
+            Ok(Box::new(BalancedPlayer::new(
+                buf,
+                BALANCED_MIN_PLIES,
+                BALANCED_MAX_PLIES,
+            )))
         }
         _ => Err(OthelloError::InvalidPlayerType),
     }
 }
 
-pub fn start_game(notation: Option<&str>, settings: GameSettings) -> Result<(), OthelloError> {
-    let mut s = StandardStream::stdout(ColorChoice::Auto);
+/// Ask for the starting layout and the handicap and return the starting board
+/// with them.
+fn board_init(s: &mut StandardStream, first_player: Disc) -> Result<Board, OthelloError> {
+    prompt(s, "Starting layout, 1. Diagonal 2. Parallel (1): ")?;
+    let mut buf = input()?;
+
+    let start = match buf.as_str() {
+        "" | "1" => StartKind::Diagonal,
+        "2" => StartKind::Parallel,
+        _ => return Err(OthelloError::InvalidStartKind),
+    };
+    prompt(s, "Handicap, corners given to a player (0): ")?;
+    buf = input()?;
+
+    let corners = match buf.trim() {
+        "" => 0,
+        n => n.parse().map_err(|_| OthelloError::InvalidHandicap)?,
+    };
+    if corners == 0 {
+        return Ok(Board::with_start(start));
+    }
+    prompt(s, "Which player gets them, `Black` or `White`? ")?;
+    buf = input()?;
+
+    let player = match buf.to_lowercase().trim() {
+        "black" => Disc::Black,
+        "white" => Disc::White,
+        _ => return Err(OthelloError::InvalidHandicap),
+    };
+
+    Board::with_handicap(start, player, corners, first_player)
+}
+
+pub fn start_game(
+    notation: Option<&str>,
+    settings: GameSettings,
+    line_reader: &LineReader,
+) -> Result<(), OthelloError> {
+    let mut s = style::stdout();
     writeln!(
         s,
         "\
 Available player types:
  1. Human
  2. Random Bot
+ 3. Adaptive Bot
+ 4. Balanced Bot, plays weaker when it's ahead
 "
     )?;
 
-    let black_player = player_init(&mut s, Disc::Black)?;
-    let white_player = player_init(&mut s, Disc::White)?;
+    let black_player = player_init(&mut s, Disc::Black, line_reader)?;
+    let white_player = player_init(&mut s, Disc::White, line_reader)?;
 
-    let mut game = if let Some(notation) = notation {
-        Game::with_board(
-            Board::from_str(notation)?,
-            white_player,
-            black_player,
-            s,
-            settings,
-        )
+    let title = if settings.game_record {
+        prompt(&mut s, "Game title (empty for the date): ")?;
+        input()?
     } else {
-        Game::new(white_player, black_player, s, settings)
+        String::new()
+    };
+
+    let board = match notation {
+        Some(notation) => Board::from_str(notation)?,
+        None => board_init(&mut s, settings.first_player)?,
     };
+
+    let mut game = Game::with_board(board, white_player, black_player, s, settings)?;
+    if !title.trim().is_empty() {
+        game.set_title(title);
+    }
+    if let Some(interrupt) = &game.settings.interrupt {
+        // a Ctrl-C before the game doesn't abort it
+        interrupt.reset();
+    }
     game.play()?;
     game.post_play()?;
 
@@ -76,63 +246,90 @@ pub fn yes_no(yes: bool) -> &'static str {
     }
 }
 
+/// How far behind a human player is suggested to resign, in the unit of
+/// [`search::evaluate`].
+const RESIGN_THRESHOLD: i16 = 30;
+
 pub fn settings_menu(
     s: &mut StandardStream,
     settings: &mut GameSettings,
 ) -> Result<(), OthelloError> {
     // TODO: save the settings as a TOML config.
 
-    write!(
+    prompt(
         s,
-        "\
+        format_args!(
+            "\
 Settings:
- 1. Show legal moves: {:3}         Show the dots on the board indicating the
-                                  legals moves of the player.
+ 1. Legal move hints: {:12}  Which legal moves of the player are dotted
+                                  on the board.
  2. Saves game directory: {}
                                   Directory where the games are saved, must be
                                   set if you enable game recordings.
  3. Game recordings: {:3}          Record the games and store them to the
                                   saves directory
+ 4. Legal moves marker: {}          The character of the dots on the board
+                                  indicating the legal moves.
+ 5. Scoring rule: {:12}    How the scores are counted at the end,
+                                  championship gives the empty squares to the
+                                  winner, disc count only counts the discs.
+ 6. First player: {:5}            Who plays the first move of the games.
+ 7. Suggest resignation: {:3}      Suggest the human players to resign when
+                                  they are far behind.
+ 8. Save format: {:4}              Format of the files of the saves.
+ 9. Abort on Ctrl-C: {:3}          Ctrl-C aborts the game and saves the moves
+                                  played, instead of quitting 0the.
+10. Incremental render: {:3}       Only redraw the squares that changed, the
+                                  terminal must support ANSI escape codes.
 
 Choose a settings to change or type `q`: \
 ",
-        yes_no(settings.show_legal_moves),
-        settings
-            .clone()
-            .saves_game_dir
-            .map(|p| p.display().to_string())
-            .unwrap_or(String::from("None")),
-        yes_no(settings.game_record)
+            match settings.legal_move_hints {
+                LegalMoveHints::None => "None",
+                LegalMoveHints::All => "All",
+                LegalMoveHints::BestOnly => "Best only",
+                LegalMoveHints::CornersOnly => "Corners only",
+            },
+            settings
+                .clone()
+                .saves_game_dir
+                .map(|p| p.display().to_string())
+                .unwrap_or(String::from("None")),
+            yes_no(settings.game_record),
+            settings.legal_move_marker,
+            match settings.scoring_rule {
+                ScoringRule::ChampionshipWinnerTakesEmpties => "Championship",
+                ScoringRule::DiscCountOnly => "Disc count",
+            },
+            settings.first_player,
+            yes_no(settings.resign_threshold.is_some()),
+            match settings.save_format {
+                SaveFormat::Json => "JSON",
+                SaveFormat::Ron => "RON",
+            },
+            yes_no(settings.interrupt.is_some()),
+            yes_no(settings.incremental_render),
+        ),
     )?;
 
-    let mut buf = String::new();
-    s.flush()?;
-    io::stdin().read_line(&mut buf)?;
-    // pop the newline character
-    buf.pop();
+    let mut buf = input()?;
     match buf.as_str() {
         "1" => {
-            buf.clear();
-            write!(s, "`Yes` or `No`? ")?;
-            s.flush()?;
-            io::stdin().read_line(&mut buf)?;
-            // pop the newline character
-            buf.pop();
-
-            settings.show_legal_moves = match buf.to_lowercase().trim() {
-                "yes" => true,
-                "no" => false,
+            prompt(s, "1. All 2. Best only 3. Corners only 4. None? ")?;
+            buf = input()?;
+
+            settings.legal_move_hints = match buf.trim() {
+                "1" => LegalMoveHints::All,
+                "2" => LegalMoveHints::BestOnly,
+                "3" => LegalMoveHints::CornersOnly,
+                "4" => LegalMoveHints::None,
                 _ => return Ok(()),
             };
         }
         "2" => todo!("implement this setting"),
         "3" => {
-            buf.clear();
-            write!(s, "`Yes` or `No`? ")?;
-            s.flush()?;
-            io::stdin().read_line(&mut buf)?;
-            // pop the newline character
-            buf.pop();
+            prompt(s, "`Yes` or `No`? ")?;
+            buf = input()?;
 
             settings.game_record = match buf.to_lowercase().trim() {
                 "yes" => true,
@@ -140,30 +337,174 @@ Choose a settings to change or type `q`: \
                 _ => return Ok(()),
             };
         }
+        "4" => {
+            prompt(s, "Marker character? ")?;
+            buf = input()?;
+
+            let mut chars = buf.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(marker), None) => settings.set_legal_move_marker(marker)?,
+                _ => return Ok(()),
+            }
+        }
+        "5" => {
+            prompt(s, "1. Championship 2. Disc count? ")?;
+            buf = input()?;
+
+            settings.scoring_rule = match buf.trim() {
+                "1" => ScoringRule::ChampionshipWinnerTakesEmpties,
+                "2" => ScoringRule::DiscCountOnly,
+                _ => return Ok(()),
+            };
+        }
+        "6" => {
+            prompt(s, "`Black` or `White`? ")?;
+            buf = input()?;
+
+            settings.first_player = match buf.to_lowercase().trim() {
+                "black" => Disc::Black,
+                "white" => Disc::White,
+                _ => return Ok(()),
+            };
+        }
+        "7" => {
+            prompt(s, "`Yes` or `No`? ")?;
+            buf = input()?;
+
+            settings.resign_threshold = match buf.to_lowercase().trim() {
+                "yes" => Some(RESIGN_THRESHOLD),
+                "no" => None,
+                _ => return Ok(()),
+            };
+        }
+        "8" => {
+            prompt(s, "1. JSON 2. RON? ")?;
+            buf = input()?;
+
+            settings.save_format = match buf.trim() {
+                "1" => SaveFormat::Json,
+                "2" => SaveFormat::Ron,
+                _ => return Ok(()),
+            };
+        }
+        "9" => {
+            prompt(s, "`Yes` or `No`? ")?;
+            buf = input()?;
+
+            settings.interrupt = match buf.to_lowercase().trim() {
+                "yes" => abort_on_ctrl_c(true)?,
+                "no" => abort_on_ctrl_c(false)?,
+                _ => return Ok(()),
+            };
+        }
+        "10" => {
+            prompt(s, "`Yes` or `No`? ")?;
+            buf = input()?;
+
+            settings.incremental_render = match buf.to_lowercase().trim() {
+                "yes" => true,
+                "no" => false,
+                _ => return Ok(()),
+            };
+        }
         _ => return Ok(()),
     }
 
     Ok(())
 }
 
+/// Read and deserialize the game save at `path`, in the format of its
+/// extension.
+fn read_save(path: &Path) -> Result<GameSave, OthelloError> {
+    let format = SaveFormat::from_path(path).unwrap_or_default();
+    GameSave::read_as(BufReader::new(File::open(path)?), format)
+}
+
+/// List the paths of the finished games saved in `saves_path`, sorted by
+/// file name so the games are in the order they were played.
+fn list_saves(saves_path: &Path) -> Result<Vec<PathBuf>, OthelloError> {
+    let mut saves: Vec<_> = fs::read_dir(saves_path)?
+        .map(|entry| entry.unwrap().path())
+        // the games being played are saved with an other extension
+        .filter(|path| path.is_file() && SaveFormat::from_path(path).is_some())
+        // the statistics of the players are stored with the saves
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name != PlayerStats::FILE_NAME)
+        })
+        .collect();
+    saves.sort();
+    Ok(saves)
+}
+
+/// Return the path of the save numbered `num` in the list printed by the
+/// replay menu, numbers start at one.
+fn select_save<'a>(
+    save_paths: &'a [(usize, PathBuf)],
+    num: &str,
+) -> Result<&'a Path, OthelloError> {
+    num.parse::<usize>()
+        .ok()
+        .and_then(|i| i.checked_sub(1))
+        .and_then(|i| save_paths.get(i))
+        .map(|(_, path)| path.as_path())
+        .ok_or_else(|| OthelloError::InvalidSaveNumber(num.to_string()))
+}
+
+/// Print where the moves of the games `left` and `right` first differ.
+fn print_diff(
+    s: &mut StandardStream,
+    (left_num, left): (&str, &GameSave),
+    (right_num, right): (&str, &GameSave),
+) -> Result<(), OthelloError> {
+    let Some(diff) = left.diff(right) else {
+        writeln!(s, "Games {left_num} and {right_num} played the same moves.")?;
+        return Ok(());
+    };
+
+    let move_num = diff.ply + 1;
+    match (diff.left, diff.right) {
+        (Some(l), Some(r)) => writeln!(
+            s,
+            "The games diverge at move {move_num}: game {left_num} played {}, game {right_num} played {}.",
+            l.to_algebric(),
+            r.to_algebric()
+        )?,
+        (None, Some(r)) => writeln!(
+            s,
+            "Game {left_num} ends after move {}, game {right_num} continues with {}.",
+            diff.ply,
+            r.to_algebric()
+        )?,
+        (Some(l), None) => writeln!(
+            s,
+            "Game {right_num} ends after move {}, game {left_num} continues with {}.",
+            diff.ply,
+            l.to_algebric()
+        )?,
+        (None, None) => unreachable!("the moves differ"),
+    }
+
+    Ok(())
+}
+
+/// Replay the game of the `transcript`, the moves one after the other, e.g:
+/// shared in a chat. It isn't saved.
+fn replay_transcript(transcript: &str) -> Result<(), OthelloError> {
+    let mut save = GameSave::from_transcript(String::from("Transcript"), transcript)?;
+    save.replay(style::stdout())
+}
+
 pub fn replay_game(s: &mut StandardStream, settings: &GameSettings) -> Result<(), OthelloError> {
     if let Some(saves_path) = &settings.saves_game_dir {
-        let save_paths: Vec<_> = fs::read_dir(&saves_path)?
-            .map(|entry| entry.unwrap().path())
-            .filter(|path| path.is_file())
-            .enumerate()
-            .collect();
+        let save_paths: Vec<_> = list_saves(saves_path)?.into_iter().enumerate().collect();
         writeln!(
             s,
             "Replay a Game, saves are located in {}",
             saves_path.display()
         )?;
         for (i, path) in &save_paths {
-            let mut save_file = File::open(&path)?;
-            let mut save_json = String::new();
-
-            save_file.read_to_string(&mut save_json)?;
-            let save = GameSave::from_json(&save_json)?;
+            let save = read_save(path)?;
 
             // keep only the file name and extension, here we unwrap it should
             // never panic because we know the save path contains the path to
@@ -171,52 +512,28 @@ pub fn replay_game(s: &mut StandardStream, settings: &GameSettings) -> Result<()
             let pretty_path = path.strip_prefix(saves_path.clone()).unwrap();
             writeln!(s, "{}. {}, {:?}", i + 1, save.title, pretty_path.display())?;
 
-            match save.end_state {
-                State::Winned {
-                    winner_color,
-                    winner_name,
-                    winner_score,
-                    loser_score,
-                } => {
-                    writeln!(
-                        s,
-                        "   {} ({}) winned, with {}-{}",
-                        winner_name, winner_color, winner_score, loser_score
-                    )?;
-                    writeln!(s)?;
-                }
-                State::Draw => {
-                    writeln!(s, "  The game ended in a draw, congrats for both of you.")?;
-                    writeln!(s)?;
-                }
-                _ => unreachable!("Not an end game state."),
-            }
+            writeln!(s, "   {}", save.end_state)?;
+            writeln!(s)?;
         }
 
-        let mut buf = String::new();
         writeln!(s)?;
-        write!(s, "What save do you want to replay? (or type `q` to quit) ")?;
-        s.flush()?;
-        io::stdin().read_line(&mut buf)?;
-        // pop the new line character
-        buf.pop();
-
-        match buf.as_str() {
-            "q" => return Ok(()),
-            int => {
-                // TODO: replace this unwrap
-                let i: usize = int.parse().unwrap();
-
-                // TODO: replace this slice sintax with `.get()` and this minus
-                // one with `checked_sub`.
-                let mut save_file = File::open(&save_paths[i - 1].1)?;
-                let mut save_json = String::new();
-
-                save_file.read_to_string(&mut save_json)?;
-                let mut save = GameSave::from_json(&save_json)?;
-                let stream = StandardStream::stdout(ColorChoice::Auto);
+        prompt(s, "What save do you want to replay? (`diff <n> <m>` to compare two saves or `q` to quit) ")?;
+        let buf = input()?;
+
+        let args = buf.split_whitespace().collect::<Vec<_>>();
+        match args.as_slice() {
+            ["q"] => return Ok(()),
+            ["diff", left_num, right_num] => {
+                let left = read_save(select_save(&save_paths, left_num)?)?;
+                let right = read_save(select_save(&save_paths, right_num)?)?;
+                print_diff(s, (left_num, &left), (right_num, &right))?;
+            }
+            [num] => {
+                let mut save = read_save(select_save(&save_paths, num)?)?;
+                let stream = style::stdout();
                 save.replay(stream)?;
             }
+            _ => return Err(OthelloError::InvalidSaveNumber(buf)),
         }
     } else {
         s.set_color(&style::ERROR)?;
@@ -226,8 +543,239 @@ pub fn replay_game(s: &mut StandardStream, settings: &GameSettings) -> Result<()
     Ok(())
 }
 
+/// Load the puzzle at `path`, show its position and check the move of the
+/// user against the solution.
+pub fn solve_puzzle(
+    s: &mut StandardStream,
+    path: &str,
+    settings: &GameSettings,
+) -> Result<(), OthelloError> {
+    let puzzle = Puzzle::from_reader(BufReader::new(File::open(path)?))?;
+
+    let legal_moves = settings.legal_move_hints.hinted_moves(
+        &puzzle.board,
+        puzzle.side,
+        puzzle.board.legal_moves(puzzle.side),
+    );
+    puzzle
+        .board
+        .render(s, legal_moves, RenderStyle::default())?;
+    writeln!(s)?;
+
+    prompt(
+        s,
+        format_args!("{} to play, find the best move: ", puzzle.side),
+    )?;
+    let mov = Move::from_algebric(input()?.trim())?;
+
+    if puzzle.check(mov) {
+        writeln!(s, "Well played, {} is the best move!", mov.to_algebric())?;
+    } else {
+        let solution = puzzle
+            .solution
+            .iter()
+            .map(|mov| mov.to_algebric())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(s, "Wrong move, the solution was {solution}.")?;
+    }
+
+    Ok(())
+}
+
+/// Evaluation swing under which a move is flagged as a potential blunder.
+const BLUNDER_SWING: i16 = -15;
+
+/// Print the evaluation of each move of the save at `path`, flagging the
+/// potential blunders.
+pub fn analyze_save(s: &mut StandardStream, path: &str) -> Result<(), OthelloError> {
+    let save = read_save(Path::new(path))?;
+
+    writeln!(s, "Analysis of {}", save.title)?;
+    for analysis in save.analyze(&search::evaluate) {
+        write!(
+            s,
+            "{:>3}. {:<5} {}  {:+4} -> {:+4}",
+            analysis.ply + 1,
+            analysis.player,
+            analysis.mov.to_algebric(),
+            analysis.before,
+            analysis.after
+        )?;
+        if analysis.swing() <= BLUNDER_SWING {
+            s.set_color(&style::ERROR)?;
+            write!(s, "  potential blunder")?;
+            s.reset()?;
+        }
+        writeln!(s)?;
+    }
+
+    Ok(())
+}
+
+/// Depth of the search ranking the moves of an imported position.
+const POSITION_ANALYSIS_DEPTH: SearchDepth = SearchDepth::Plies(4);
+
+/// Print the legal moves of the `side` to move in the position written in
+/// Othello Notation, ranked by their evaluation.
+pub fn analyze_position(
+    s: &mut impl WriteColor,
+    notation: &str,
+    side: &str,
+) -> Result<(), OthelloError> {
+    let board = Board::from_str(notation)?;
+    let player = match side.to_lowercase().as_str() {
+        "x" | "black" => Disc::Black,
+        "o" | "white" => Disc::White,
+        _ => return Err(OthelloError::InvalidPlayerColor(side.to_string())),
+    };
+
+    board.render(s, Some(board.legal_moves(player)), RenderStyle::default())?;
+    writeln!(s)?;
+
+    match board.outcome() {
+        Some(Outcome::Win(winner)) => {
+            writeln!(
+                s,
+                "The game is over, {winner} wins by {}.",
+                board.disc_diff(winner)
+            )?;
+            return Ok(());
+        }
+        Some(Outcome::Draw) => {
+            writeln!(s, "The game is over, it's a draw.")?;
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let ranked = search::rank_moves(&board, player, POSITION_ANALYSIS_DEPTH);
+    if ranked.is_empty() {
+        writeln!(s, "{player} can't move and must pass, {} plays.", !player)?;
+        return Ok(());
+    }
+    writeln!(s, "Moves of {player}, the best first:")?;
+    for (mov, value) in ranked {
+        writeln!(s, "  {}  {value:>12}", mov.to_algebric())?;
+    }
+
+    Ok(())
+}
+
+/// Print the win rates of black and white for each opening of the saved games.
+pub fn print_stats(s: &mut StandardStream, settings: &GameSettings) -> Result<(), OthelloError> {
+    let Some(saves_path) = &settings.saves_game_dir else {
+        s.set_color(&style::ERROR)?;
+        writeln!(s, "The game save directory isn't set.")?;
+        s.reset()?;
+        return Ok(());
+    };
+
+    let players = PlayerStats::load(&saves_path.join(PlayerStats::FILE_NAME))?;
+    let leaderboard = players.leaderboard();
+    if !leaderboard.is_empty() {
+        writeln!(
+            s,
+            "{:<20} {:>5} {:>5} {:>6} {:>5} {:>8}",
+            "Player", "Games", "Wins", "Losses", "Draws", "Win rate"
+        )?;
+        for (name, record) in leaderboard {
+            writeln!(
+                s,
+                "{:<20} {:>5} {:>5} {:>6} {:>5} {:>7.0}%",
+                style::truncate_to_width(name, 20),
+                record.games,
+                record.wins,
+                record.losses,
+                record.draws,
+                record.win_rate() * 100.0
+            )?;
+        }
+        writeln!(s)?;
+    }
+
+    let saves = list_saves(saves_path)?
+        .iter()
+        .map(|path| read_save(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let stats = opening_stats(&saves);
+    if stats.is_empty() {
+        writeln!(s, "No finished game to compute statistics from.")?;
+        return Ok(());
+    }
+
+    writeln!(
+        s,
+        "{:<6} {:<14} {:>5} {:>10} {:>10} {:>5}",
+        "Moves", "Opening", "Games", "Black wins", "White wins", "Draws"
+    )?;
+    for (moves, stat) in &stats {
+        let name = moves
+            .split(' ')
+            .map(Move::from_algebric)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .and_then(|moves| opening_name(&moves))
+            .unwrap_or("");
+        writeln!(
+            s,
+            "{:<6} {:<14} {:>5} {:>9.0}% {:>9.0}% {:>5}",
+            moves,
+            name,
+            stat.games,
+            stat.black_win_rate() * 100.0,
+            stat.white_win_rate() * 100.0,
+            stat.draws
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Check that every save of the directory replays to its recorded end state,
+/// and report the ones that don't.
+pub fn verify_saves(s: &mut StandardStream, settings: &GameSettings) -> Result<(), OthelloError> {
+    let Some(saves_path) = &settings.saves_game_dir else {
+        s.set_color(&style::ERROR)?;
+        writeln!(s, "The game save directory isn't set.")?;
+        s.reset()?;
+        return Ok(());
+    };
+
+    let save_paths = list_saves(saves_path)?;
+    let mut failed = 0;
+    for path in &save_paths {
+        if let Err(e) = read_save(path).and_then(|save| save.verify()) {
+            failed += 1;
+            s.set_color(&style::ERROR)?;
+            writeln!(s, "{}: {e}", path.display())?;
+            s.reset()?;
+        }
+    }
+    writeln!(s, "{} saves verified, {failed} failed.", save_paths.len())?;
+
+    Ok(())
+}
+
+/// Run the `command`, if it panics the panic is returned as an error so the
+/// session goes on with the next command.
+fn catch_panic(command: impl FnOnce() -> Result<(), OthelloError>) -> Result<(), OthelloError> {
+    // the command only mutates the stream and the settings, they stay usable
+    // even if it panicked in the middle.
+    panic::catch_unwind(AssertUnwindSafe(command)).unwrap_or_else(|payload| {
+        let msg = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => payload
+                .downcast_ref::<&str>()
+                .map_or("unknown panic payload", |msg| msg)
+                .to_string(),
+        };
+        Err(OthelloError::Panicked(msg))
+    })
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut s = StandardStream::stdout(ColorChoice::Auto);
+    let mut s = style::stdout();
 
     writeln!(s, "Welcome, in Zerothe!\n")?;
     let help = format!(
@@ -238,7 +786,18 @@ fn main() -> Result<(), Box<dyn Error>> {
 COMMANDS:
     play, p             Start a new game
     import <notation>   Import a game using the Othello Notation
+    import <notation> --analyze [black|white]
+                        Rank the moves of the side to move in the position,
+                        black by default
     replay, r           Replay a previously saved game
+    play-transcript <moves>
+                        Replay the game of the moves written one after the
+                        other, e.g: `f5d6c3`, without saving it
+    analyze <file>      Evaluate each move of a saved game
+    puzzle <file>       Solve the puzzle stored in the json file
+    stats               Print the leaderboard of the players and the win
+                        rates by opening of the saved games
+    verify              Check that the saved games replay to their result
     set                 Alter 0the settings
     rules               Print the rules of Othello
     license             Print the license of the program
@@ -251,25 +810,36 @@ COMMANDS:
     );
 
     let mut settings = GameSettings::default();
+    let line_reader = line_reader();
 
-    let mut cmd = String::new();
     loop {
-        write!(s, "Command (h for help): ")?;
-        io::stdout().flush()?;
-        cmd.clear();
-        io::stdin().read_line(&mut cmd)?;
-        // remove the newline
-        cmd.pop();
+        s.flush()?;
+        let Some(cmd) = line_reader.read_line(&settings.messages.command_prompt)? else {
+            // end of the input, e.g: Ctrl-D
+            writeln!(s)?;
+            break;
+        };
 
         let vec = cmd.split_whitespace().collect::<Vec<_>>();
         let args = vec.as_slice();
 
         let res = match args {
             // TODO: don't clone the settings but use some kind of (smart) pointer
-            ["play" | "p"] => start_game(None, settings.clone()),
-            ["import", notation] => start_game(Some(notation), settings.clone()),
-            ["replay" | "r"] => replay_game(&mut s, &settings),
-            ["set"] => settings_menu(&mut s, &mut settings),
+            ["play" | "p"] => catch_panic(|| start_game(None, settings.clone(), &line_reader)),
+            ["import", notation] => {
+                catch_panic(|| start_game(Some(notation), settings.clone(), &line_reader))
+            }
+            ["import", notation, "--analyze"] => analyze_position(&mut s, notation, "black"),
+            ["import", notation, "--analyze", side] => analyze_position(&mut s, notation, side),
+            ["replay" | "r"] => catch_panic(|| replay_game(&mut s, &settings)),
+            ["play-transcript", moves @ ..] if !moves.is_empty() => {
+                catch_panic(|| replay_transcript(&moves.concat()))
+            }
+            ["analyze", path] => analyze_save(&mut s, path),
+            ["puzzle", path] => solve_puzzle(&mut s, path, &settings),
+            ["stats"] => print_stats(&mut s, &settings),
+            ["verify"] => verify_saves(&mut s, &settings),
+            ["set"] => catch_panic(|| settings_menu(&mut s, &mut settings)),
             ["rules"] => {
                 writeln!(s, "{}", OTHELLO_RULES)?;
                 Ok(())
@@ -302,3 +872,82 @@ COMMANDS:
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, io::Cursor, rc::Rc};
+
+    use termcolor::NoColor;
+
+    use super::*;
+
+    #[test]
+    fn raw_lines_are_read_after_the_prompt() {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let mut input = Cursor::new("f5\r\n\nquit");
+        let reader = LineReader::new({
+            let output = output.clone();
+            move |msg| read_raw_line(&mut *output.borrow_mut(), &mut input, msg)
+        });
+
+        assert_eq!(reader.read_line("Black: ").unwrap().as_deref(), Some("f5"));
+        assert_eq!(reader.read_line("White: ").unwrap().as_deref(), Some(""));
+        assert_eq!(
+            reader.read_line("Black: ").unwrap().as_deref(),
+            Some("quit")
+        );
+        assert_eq!(reader.read_line("White: ").unwrap(), None);
+        assert_eq!(*output.borrow(), b"Black: White: Black: White: ");
+    }
+
+    #[test]
+    fn panics_are_returned_as_errors() {
+        assert!(catch_panic(|| Ok(())).is_ok());
+        assert!(matches!(
+            catch_panic(|| Err(OthelloError::EndOfInput)),
+            Err(OthelloError::EndOfInput)
+        ));
+
+        // the payload is a `&str` or a `String` if the message is formatted
+        let res = catch_panic(|| panic!("the board is broken"));
+        assert!(matches!(res, Err(OthelloError::Panicked(msg)) if msg == "the board is broken"));
+        let square = "d4";
+        let res = catch_panic(|| panic!("the square {square} is broken"));
+        assert!(
+            matches!(res, Err(OthelloError::Panicked(msg)) if msg == "the square d4 is broken")
+        );
+    }
+
+    /// Return what `analyze_position` writes on the board in `notation`.
+    fn analysis(notation: &str, side: &str) -> String {
+        let mut s = NoColor::new(Vec::new());
+        analyze_position(&mut s, notation, side).unwrap();
+        String::from_utf8(s.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn position_analysis_ranks_the_moves() {
+        // Black takes the corner a1 and wins, the other move c8 lets White
+        // play e1
+        let corner = format!("-OOX----{}XO------", "-".repeat(48));
+        let report = analysis(&corner, "black");
+        let (_, moves) = report
+            .split_once("Moves of Black, the best first:\n")
+            .unwrap();
+        let moves: Vec<_> = moves.lines().map(str::split_whitespace).collect();
+        assert_eq!(moves.len(), 2);
+        assert!(moves[0].clone().eq(["a1", "wins", "by", "7"]));
+        assert_eq!(moves[1].clone().next(), Some("c8"));
+    }
+
+    #[test]
+    fn position_analysis_reports_passes_and_ends() {
+        // the White disc of a1 can't be taken
+        let pass = format!("OX{}", "-".repeat(62));
+        assert!(analysis(&pass, "x").ends_with("Black can't move and must pass, White plays.\n"));
+        assert!(analysis(&pass, "o").contains("Moves of White, the best first:\n  c1"));
+
+        let over = format!("XXXX{}", "-".repeat(60));
+        assert!(analysis(&over, "white").ends_with("The game is over, Black wins by 4.\n"));
+    }
+}